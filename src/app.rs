@@ -1,26 +1,203 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
 use eframe::egui;
-use egui::{emath, Color32, Frame, Pos2, Rect, RichText, Window};
+use egui::plot::{Bar, BarChart, Plot};
+use egui::{Color32, RichText, Window};
 
+use crate::automata::EliminationOrder;
+use crate::automata::NfaStates;
 use crate::automata::DFA;
+use crate::automata::Gnfa;
+use crate::automata::Homomorphism;
 use crate::automata::NFA;
+use crate::automata::PumpingDecomposition;
 use crate::automata::ReOperator;
-use crate::display::DisplayGraphParameter;
+use crate::automata::word_to_string;
 use crate::display::Visualizer;
+use crate::error::InvalidHomomorphism;
+use crate::grammar::EquationSystem;
+use crate::grammar::{Grammar, LL1Table, LRKind, LRTable, ParseResult, ParseStep, Terminal};
+use crate::learning::{learn_lstar, learn_rpni, LStarStep, RegexOracle, Samples};
 use crate::utils::Graph;
 
+/// language operations of the "Operations" menu, applied to the current automaton
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    LeftQuotientByWord,
+    RightQuotientByWord,
+    LeftQuotient,
+    RightQuotient,
+    PrefixClosure,
+    SuffixClosure,
+    FactorClosure,
+    Shuffle,
+    Union,
+    Concatenation,
+    Star,
+    Plus,
+}
+
+impl Operation {
+    const ALL: [Operation; 12] = [
+        Operation::LeftQuotientByWord,
+        Operation::RightQuotientByWord,
+        Operation::LeftQuotient,
+        Operation::RightQuotient,
+        Operation::PrefixClosure,
+        Operation::SuffixClosure,
+        Operation::FactorClosure,
+        Operation::Shuffle,
+        Operation::Union,
+        Operation::Concatenation,
+        Operation::Star,
+        Operation::Plus,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Operation::LeftQuotientByWord => "Left quotient by word",
+            Operation::RightQuotientByWord => "Right quotient by word",
+            Operation::LeftQuotient => "Left quotient by regex",
+            Operation::RightQuotient => "Right quotient by regex",
+            Operation::PrefixClosure => "Prefix closure",
+            Operation::SuffixClosure => "Suffix closure",
+            Operation::FactorClosure => "Factor closure",
+            Operation::Shuffle => "Shuffle with regex",
+            Operation::Union => "Union with regex",
+            Operation::Concatenation => "Concatenation with regex",
+            Operation::Star => "Kleene star",
+            Operation::Plus => "Kleene plus",
+        }
+    }
+}
+
+/// transformations of the grammar editor, the result is shown next to the original grammar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GrammarTransformation {
+    RemoveEpsilon,
+    RemoveUseless,
+    RemoveUnitaryCycles,
+    Itemization,
+}
+
+impl GrammarTransformation {
+    const ALL: [GrammarTransformation; 4] = [
+        GrammarTransformation::RemoveEpsilon,
+        GrammarTransformation::RemoveUseless,
+        GrammarTransformation::RemoveUnitaryCycles,
+        GrammarTransformation::Itemization,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            GrammarTransformation::RemoveEpsilon => "Remove ε productions",
+            GrammarTransformation::RemoveUseless => "Remove useless symbols",
+            GrammarTransformation::RemoveUnitaryCycles => "Remove unit productions",
+            GrammarTransformation::Itemization => "LR(0) items",
+        }
+    }
+}
+
 pub struct EguiApp {
     error: Option<String>,
     regex_text: String,
+    hide_dead_states: bool,
 
     // This is indexed accordingly
     // 0: Regex
     // 1: NFA
-    // 2: DFA
-    // 3: Minimized DFA
+    // 2: ε-free NFA
+    // 3: DFA
+    // 4: Minimized DFA
     // a union structure would be useful for accessing the Visualizers
     // with both indixes and names, but it's problematic how to do it
     // in rust.
-    to_visualize: [Visualizer; 4],
+    to_visualize: [Visualizer; 5],
+    // alternative minimization pipeline, it shows the four automata it builds
+    brzozowski_visualizer: Visualizer,
+    brzozowski_is_isomorphic: Option<bool>,
+
+    // minimized DFA of the last generated regex, the language
+    // properties are computed on this automaton
+    automaton: Option<DFA<NfaStates>>,
+    // one line for each property, computed when the automaton is generated
+    language_properties: Vec<String>,
+    inclusion_regex_text: String,
+    inclusion_result: Option<String>,
+    num_words_to_show: usize,
+    histogram_max_len: usize,
+    // computed when the automaton or the sliders change, not at every frame
+    shortlex_words: Vec<String>,
+    words_by_length: Vec<usize>,
+    growth_rate: f64,
+    // Myhill-Nerode table of the automaton, computed when it's generated
+    access_words: Vec<Option<String>>,
+    distinguishing_suffixes: BTreeMap<(usize, usize), String>,
+    // pair of states selected in the Myhill-Nerode table
+    selected_pair: Option<(usize, usize)>,
+
+    elimination_order: EliminationOrder,
+    converted_regex: Option<String>,
+    elimination_visualizer: Visualizer,
+
+    // steps of the solution of the right linear equations of the DFA
+    arden_steps: Vec<String>,
+    arden_regex: Option<String>,
+    is_arden_win_open: bool,
+
+    // parameters of the operations menu, every result is shown in a new window
+    operation_word: String,
+    operation_regex_text: String,
+    operation_windows: Vec<Visualizer>,
+    num_operations: usize,
+    // rows (character, image) of the homomorphism editor
+    homomorphism_rows: Vec<(String, String)>,
+
+    pumping_word: String,
+    pumping_decomposition: Option<PumpingDecomposition>,
+    pumping_times: usize,
+    pumping_visualizer: Visualizer,
+
+    // target regex of the L* oracle, and the steps of the last run
+    lstar_regex_text: String,
+    lstar_steps: Vec<LStarStep>,
+    lstar_current_step: usize,
+    lstar_summary: Option<String>,
+    is_lstar_win_open: bool,
+    lstar_visualizer: Visualizer,
+
+    // labelled samples of RPNI, one "+ word" or "- word" for each line
+    rpni_samples_text: String,
+    rpni_file_path: String,
+    rpni_summary: Option<String>,
+    rpni_visualizer: Visualizer,
+
+    // text of the grammar editor and the grammar parsed from it
+    grammar_text: String,
+    grammar: Option<Grammar>,
+    grammar_nullable: String,
+    // rows (non terminal, FIRST, FOLLOW) of the table
+    grammar_sets: Vec<(String, String, String)>,
+    // name of the last transformation, the grammar before and after it
+    grammar_transformation: Option<(String, String, String)>,
+    is_grammar_win_open: bool,
+    ll1_table: Option<LL1Table>,
+    is_ll1_win_open: bool,
+    lr0_visualizer: Visualizer,
+    lr_kind: LRKind,
+    lr_table: Option<LRTable>,
+    // number of states of the three kinds of LR tables
+    lr_state_counts: Option<String>,
+    is_lr_win_open: bool,
+
+    // input of the parsers, terminals separated by spaces, and the trace of the last run
+    parse_input: String,
+    parse_steps: Vec<ParseStep>,
+    parse_errors: Vec<String>,
+    parse_title: String,
+    is_parse_win_open: bool,
+    parse_tree_visualizer: Visualizer,
 }
 
 impl Default for EguiApp {
@@ -28,13 +205,82 @@ impl Default for EguiApp {
         Self {
             error: None,
             regex_text: String::new(),
+            hide_dead_states: false,
 
             to_visualize: [
                 Visualizer::new("Regex Syntax Tree".to_string()),
                 Visualizer::new("NFA".to_string()),
+                Visualizer::new("ε-free NFA".to_string()),
                 Visualizer::new("DFA".to_string()),
                 Visualizer::new("Minimized DFA".to_string()),
             ],
+            brzozowski_visualizer: Visualizer::new("Brzozowski minimization".to_string()),
+            brzozowski_is_isomorphic: None,
+
+            automaton: None,
+            language_properties: Vec::new(),
+            inclusion_regex_text: String::new(),
+            inclusion_result: None,
+            num_words_to_show: 10,
+            histogram_max_len: 10,
+            shortlex_words: Vec::new(),
+            words_by_length: Vec::new(),
+            growth_rate: 0.,
+            access_words: Vec::new(),
+            distinguishing_suffixes: BTreeMap::new(),
+            selected_pair: None,
+
+            elimination_order: EliminationOrder::FewestEdges,
+            converted_regex: None,
+            elimination_visualizer: Visualizer::new("State elimination".to_string()),
+
+            arden_steps: Vec::new(),
+            arden_regex: None,
+            is_arden_win_open: false,
+
+            operation_word: String::new(),
+            operation_regex_text: String::new(),
+            operation_windows: Vec::new(),
+            num_operations: 0,
+            homomorphism_rows: vec![("a".to_string(), "ab".to_string())],
+
+            pumping_word: String::new(),
+            pumping_decomposition: None,
+            pumping_times: 1,
+            pumping_visualizer: Visualizer::new("Pumping lemma".to_string()),
+
+            lstar_regex_text: String::new(),
+            lstar_steps: Vec::new(),
+            lstar_current_step: 0,
+            lstar_summary: None,
+            is_lstar_win_open: false,
+            lstar_visualizer: Visualizer::new("L* hypotheses".to_string()),
+
+            rpni_samples_text: "+ ab\n+ abab\n- a\n- b\n- aba".to_string(),
+            rpni_file_path: String::new(),
+            rpni_summary: None,
+            rpni_visualizer: Visualizer::new("RPNI".to_string()),
+
+            grammar_text: "E -> T E'\nE' -> + T E' | ε\nT -> F T'\nT' -> * F T' | ε\nF -> ( E ) | id".to_string(),
+            grammar: None,
+            grammar_nullable: String::new(),
+            grammar_sets: Vec::new(),
+            grammar_transformation: None,
+            is_grammar_win_open: false,
+            ll1_table: None,
+            is_ll1_win_open: false,
+            lr0_visualizer: Visualizer::new("LR(0) automaton".to_string()),
+            lr_kind: LRKind::SLR1,
+            lr_table: None,
+            lr_state_counts: None,
+            is_lr_win_open: false,
+
+            parse_input: "id + id * id".to_string(),
+            parse_steps: Vec::new(),
+            parse_errors: Vec::new(),
+            parse_title: String::new(),
+            is_parse_win_open: false,
+            parse_tree_visualizer: Visualizer::new("Parse tree".to_string()),
         }
     }
 }
@@ -44,92 +290,1204 @@ impl EguiApp {
         Self::default()
     }
 
-    pub fn get_converter(index: i32) -> impl Fn(ReOperator) -> Graph {
+    /// returns the function that builds the graph of the index-th visualizer,
+    /// hide_dead_states trims the DFAs, removing the dead states (like the empty set one)
+    pub fn get_converter(index: i32, hide_dead_states: bool) -> Box<dyn Fn(ReOperator) -> Graph> {
+        let show_dfa = move |dfa: DFA<NfaStates>| {
+            if hide_dead_states {
+                dfa.trim().into()
+            } else {
+                dfa.into()
+            }
+        };
+
         match index {
-            0 => |re: ReOperator| re.into(),
-            1 => |re: ReOperator| NFA::from(&re).into(),
-            2 => |re: ReOperator| DFA::from(&NFA::from(&re)).into(),
-            3 => |re: ReOperator| DFA::from(&NFA::from(&re)).get_minimized_dfa().into(),
+            0 => Box::new(|re: ReOperator| re.into()),
+            1 => Box::new(|re: ReOperator| NFA::from(&re).into()),
+            2 => Box::new(|re: ReOperator| NFA::from(&re).remove_epsilon().remove_useless_states().into()),
+            3 => Box::new(move |re: ReOperator| show_dfa(DFA::from(&NFA::from(&re)))),
+            4 => Box::new(move |re: ReOperator| show_dfa(DFA::from(&NFA::from(&re)).get_minimized_dfa())),
             _ => panic!("Invalid index"),
         }
     }
+
+    /// updates the automaton inspected by the other panels, and clears their results
+    fn set_automaton(&mut self, re: &ReOperator) {
+        let dfa = DFA::from(&NFA::from(re)).get_minimized_dfa();
+        let yes_no = |value: bool| if value { "yes" } else { "no" };
+        self.language_properties = vec![
+            format!("states of the minimized DFA: {}", dfa.get_num_states()),
+            format!("empty: {}", yes_no(dfa.is_empty())),
+            format!("universal: {}", yes_no(dfa.is_universal())),
+            format!("finite: {}", yes_no(dfa.is_finite())),
+            match dfa.cardinality() {
+                Some(count) => format!("number of words: {}", count),
+                None => "number of words: infinite".to_string(),
+            },
+        ];
+        self.growth_rate = dfa.growth_rate();
+        self.access_words = dfa.get_access_words();
+        self.distinguishing_suffixes = dfa.get_distinguishing_suffixes();
+        self.automaton = Some(dfa);
+        self.update_accepted_words();
+        self.inclusion_result = None;
+        self.converted_regex = None;
+        self.arden_regex = None;
+        self.selected_pair = None;
+        self.pumping_decomposition = None;
+    }
+
+    fn visualizer_options_ui(ui: &mut egui::Ui, visualizer: &mut Visualizer) {
+        ui.collapsing(
+            format!("{} visualizer option", visualizer.box_title),
+            |ui| {
+                ui.add(
+                    egui::Slider::new(&mut visualizer.padding_x, 10.0..=100.0)
+                        .text("padding x"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut visualizer.padding_y, 10.0..=100.0)
+                        .text("padding y"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut visualizer.size_node, 10.0..=100.0)
+                        .text("node size"),
+                );
+            },
+        );
+    }
+
+    fn language_properties_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Language properties");
+        let dfa = match &self.automaton {
+            Some(dfa) => dfa,
+            None => {
+                ui.label("generate an automaton to see its properties");
+                return;
+            }
+        };
+
+        for property in self.language_properties.iter() {
+            ui.label(property);
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("is subset of");
+            ui.text_edit_singleline(&mut self.inclusion_regex_text)
+                .on_hover_text("Enter the regular expression of the other language");
+        });
+        if ui.button("Check inclusion").clicked() {
+            self.inclusion_result = match ReOperator::from_string(&self.inclusion_regex_text) {
+                Ok(re) => {
+                    let other = DFA::from(&NFA::from(&re)).get_minimized_dfa();
+                    if dfa.is_subset_of(&other) {
+                        Some("yes".to_string())
+                    } else {
+                        // the witness exists when the language is not a subset
+                        let word = dfa.get_subset_witness(&other).unwrap();
+                        Some(format!("no, \"{}\" is not in the other language", word))
+                    }
+                }
+                Err(e) => Some(e.to_string()),
+            };
+        }
+        if let Some(result) = &self.inclusion_result {
+            ui.label(result);
+        }
+    }
+
+    /// computes the words and the counts shown by the accepted words panel
+    fn update_accepted_words(&mut self) {
+        if let Some(dfa) = &self.automaton {
+            self.shortlex_words = dfa
+                .shortlex_words()
+                .take(self.num_words_to_show)
+                .map(|word| word_to_string(&word))
+                .collect();
+            self.words_by_length = dfa.count_words_by_length(self.histogram_max_len);
+        }
+    }
+
+    fn accepted_words_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Accepted words");
+        if self.automaton.is_none() {
+            ui.label("generate an automaton to see its words");
+            return;
+        }
+
+        let mut has_changed = ui
+            .add(egui::Slider::new(&mut self.num_words_to_show, 1..=100).text("words to show"))
+            .changed();
+        if self.shortlex_words.is_empty() {
+            ui.label("the language is empty");
+        } else {
+            ui.label(self.shortlex_words.join(", "));
+        }
+
+        has_changed |= ui
+            .add(egui::Slider::new(&mut self.histogram_max_len, 1..=30).text("max word length"))
+            .changed();
+        let bars = self
+            .words_by_length
+            .iter()
+            .enumerate()
+            .map(|(len, count)| Bar::new(len as f64, *count as f64).name(format!("length {}", len)))
+            .collect();
+        Plot::new("words_by_length")
+            .height(150.)
+            .allow_scroll(false)
+            .show(ui, |plot_ui| plot_ui.bar_chart(BarChart::new(bars)));
+
+        ui.label(format!("growth rate: {:.4}", self.growth_rate))
+            .on_hover_text("the number of words of length n grows like rate^n");
+
+        if has_changed {
+            self.update_accepted_words();
+        }
+    }
+
+    fn myhill_nerode_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Myhill-Nerode equivalence classes");
+        let dfa = match &self.automaton {
+            Some(dfa) => dfa,
+            None => {
+                ui.label("generate an automaton to see its equivalence classes");
+                return;
+            }
+        };
+
+        let raw_access_words = &self.access_words;
+        let access_words: Vec<String> = raw_access_words
+            .iter()
+            .map(|word| word.as_ref().map_or("-".to_string(), |word| word_to_string(word)))
+            .collect();
+        let suffixes = &self.distinguishing_suffixes;
+
+        ui.label(format!(
+            "{} classes, each state of the minimized DFA is a class [access word]. \
+            The table shows the shortest suffix that distinguishes two states, click on it for details.",
+            dfa.get_num_states()
+        ));
+        egui::ScrollArea::horizontal()
+            .id_source("myhill_nerode_scroll")
+            .show(ui, |ui| {
+                egui::Grid::new("myhill_nerode_table")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("");
+                        for (state, word) in access_words.iter().enumerate() {
+                            ui.label(format!("{} [{}]", state, word));
+                        }
+                        ui.end_row();
+
+                        for (i, word) in access_words.iter().enumerate() {
+                            ui.label(format!("{} [{}]", i, word));
+                            for j in 0..access_words.len() {
+                                match suffixes.get(&(i, j)) {
+                                    Some(suffix) => {
+                                        let is_selected = self.selected_pair == Some((i, j));
+                                        if ui.selectable_label(is_selected, word_to_string(suffix)).clicked() {
+                                            self.selected_pair = Some((i, j));
+                                        }
+                                    }
+                                    None => {
+                                        ui.label("");
+                                    }
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if let Some((i, j)) = self.selected_pair {
+            let suffix = &suffixes[&(i, j)];
+            let verdict = |state: usize| {
+                let prefix = raw_access_words[state].clone().unwrap_or_default();
+                let word = format!("{}{}", prefix, suffix);
+                let accepted = if dfa.accepts(&word) { "accepted" } else { "rejected" };
+                format!("{} is {}", word_to_string(&word), accepted)
+            };
+            ui.label(format!(
+                "states {} and {} are distinguished by {}: {}, while {}",
+                i,
+                j,
+                word_to_string(suffix),
+                verdict(i),
+                verdict(j)
+            ));
+        }
+    }
+
+    fn state_elimination_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("DFA to regex");
+        let dfa = match &self.automaton {
+            Some(dfa) => dfa,
+            None => {
+                ui.label("generate an automaton to convert it back to a regex");
+                return;
+            }
+        };
+
+        egui::ComboBox::from_label("elimination order")
+            .selected_text(self.elimination_order.name())
+            .show_ui(ui, |ui| {
+                for order in EliminationOrder::ALL {
+                    ui.selectable_value(&mut self.elimination_order, order, order.name());
+                }
+            });
+
+        if ui.button("Eliminate states").clicked() {
+            let (regex, steps) = Gnfa::from(dfa).into_regex_with_steps(self.elimination_order);
+            self.elimination_visualizer.set_steps(
+                steps
+                    .into_iter()
+                    .map(|(description, graph)| (description, graph.into()))
+                    .collect(),
+            );
+            self.converted_regex = regex.map(|regex| regex.to_string());
+            if self.converted_regex.is_none() {
+                self.error = Some("the language is empty, it has no regex".to_string());
+            }
+        }
+
+        if let Some(regex) = &self.converted_regex {
+            ui.label(format!("state elimination: {}", regex));
+            if ui.button("Use as regex").clicked() {
+                self.regex_text = regex.clone();
+            }
+        }
+
+        if ui.button("Solve equations (Arden's lemma)").clicked() {
+            match EquationSystem::from_grammar(&Grammar::from(dfa)) {
+                Ok(system) => {
+                    let (regex, steps) = system.solve();
+                    self.arden_steps = steps;
+                    self.arden_regex = regex.map(|regex| regex.to_string());
+                    self.is_arden_win_open = true;
+                    if self.arden_regex.is_none() {
+                        self.error = Some("the language is empty, it has no regex".to_string());
+                    }
+                }
+                Err(e) => self.error = Some(e.to_string()),
+            }
+        }
+
+        if let Some(regex) = &self.arden_regex {
+            ui.label(format!("equations: {}", regex));
+            if ui.button("Use as regex").clicked() {
+                self.regex_text = regex.clone();
+            }
+        }
+    }
+
+    /// applies the operation to the current automaton, the operations with
+    /// a language parameter use the minimized DFA of the operation regex
+    fn apply_operation(&self, operation: Operation) -> Result<Graph, Box<dyn Error>> {
+        let dfa = match &self.automaton {
+            Some(dfa) => dfa,
+            None => return Err("generate an automaton before applying an operation".into()),
+        };
+        let other = || -> Result<DFA<NfaStates>, Box<dyn Error>> {
+            let re = ReOperator::from_string(&self.operation_regex_text)?;
+            Ok(DFA::from(&NFA::from(&re)).get_minimized_dfa())
+        };
+        // the combinators of the NFAs use the Thompson NFA of the regex
+        let other_nfa = || -> Result<NFA, Box<dyn Error>> {
+            let re = ReOperator::from_string(&self.operation_regex_text)?;
+            Ok(NFA::from(&re))
+        };
+
+        let graph = match operation {
+            Operation::LeftQuotientByWord => dfa.left_quotient_by_word(&self.operation_word).remove_unreachable().into(),
+            Operation::RightQuotientByWord => dfa.right_quotient_by_word(&self.operation_word).into(),
+            Operation::LeftQuotient => dfa.left_quotient(&other()?).remove_useless_states().into(),
+            Operation::RightQuotient => dfa.right_quotient(&other()?).into(),
+            Operation::PrefixClosure => dfa.prefix_closure().into(),
+            Operation::SuffixClosure => dfa.suffix_closure().remove_useless_states().into(),
+            Operation::FactorClosure => dfa.factor_closure().remove_useless_states().into(),
+            Operation::Shuffle => dfa.shuffle(&other()?).into(),
+            Operation::Union => NFA::from_dfa(dfa).union(&other_nfa()?).into(),
+            Operation::Concatenation => NFA::from_dfa(dfa).concat(&other_nfa()?).into(),
+            Operation::Star => NFA::from_dfa(dfa).star().into(),
+            Operation::Plus => NFA::from_dfa(dfa).plus().into(),
+        };
+        Ok(graph)
+    }
+
+    fn pumping_lemma_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Pumping lemma");
+        let dfa = match &self.automaton {
+            Some(dfa) => dfa,
+            None => {
+                ui.label("generate an automaton to pump its words");
+                return;
+            }
+        };
+        let pumping_length = dfa.get_pumping_length();
+        ui.label(format!("pumping length (states of the trimmed minimized DFA): {}", pumping_length));
+
+        ui.horizontal(|ui| {
+            ui.label("word");
+            ui.text_edit_singleline(&mut self.pumping_word);
+        });
+        ui.horizontal(|ui| {
+            if ui
+                .button("Suggest a word")
+                .on_hover_text("the first accepted word at least as long as the pumping length")
+                .clicked()
+            {
+                // a finite language has no such word, and the iterator ends
+                match dfa.shortlex_words().find(|word| word.chars().count() >= pumping_length) {
+                    Some(word) => self.pumping_word = word,
+                    None => self.error = Some("the language is finite, no word can be pumped".to_string()),
+                }
+            }
+            if ui.button("Decompose").clicked() {
+                self.pumping_decomposition = dfa.get_pumping_decomposition(&self.pumping_word);
+                match &self.pumping_decomposition {
+                    Some(decomposition) => {
+                        let graph: Graph = dfa.clone().into();
+                        self.pumping_visualizer.set_graph(graph.into());
+                        self.pumping_visualizer.set_highlight(
+                            decomposition.loop_states.iter().cloned().collect(),
+                            decomposition.get_loop_edges().into_iter().collect(),
+                        );
+                        self.error = None;
+                    }
+                    None if !dfa.accepts(&self.pumping_word) => {
+                        self.error = Some(format!("\"{}\" is not accepted", self.pumping_word))
+                    }
+                    None => {
+                        self.error = Some(format!(
+                            "\"{}\" visits no state twice, it's shorter than the pumping length",
+                            self.pumping_word
+                        ))
+                    }
+                }
+            }
+        });
+
+        if let Some(decomposition) = &self.pumping_decomposition {
+            ui.label(format!(
+                "x = {}, y = {}, z = {}",
+                word_to_string(&decomposition.x),
+                word_to_string(&decomposition.y),
+                word_to_string(&decomposition.z)
+            ));
+            ui.add(egui::Slider::new(&mut self.pumping_times, 0..=10).text("i"));
+
+            let pumped = decomposition.pump(self.pumping_times);
+            let accepted = dfa.accepts(&pumped);
+            ui.label(format!("x y^{} z = {}", self.pumping_times, word_to_string(&pumped)));
+            ui.label(
+                RichText::new(if accepted { "accepted" } else { "rejected" })
+                    .color(if accepted { Color32::GREEN } else { Color32::RED }),
+            );
+        }
+    }
+
+    fn lstar_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Automata learning (L*)");
+        ui.horizontal(|ui| {
+            ui.label("target regex");
+            ui.text_edit_singleline(&mut self.lstar_regex_text)
+                .on_hover_text("The oracle answers the queries with the language of this regex");
+        });
+
+        if ui.button("Learn with L*").clicked() {
+            match ReOperator::from_string(&self.lstar_regex_text) {
+                Ok(re) => {
+                    let oracle = RegexOracle::new(&re);
+                    let (learned, steps) = learn_lstar(&oracle);
+                    self.lstar_summary = Some(format!(
+                        "learned {} states with {} membership and {} equivalence queries, isomorphic to the target: {}",
+                        learned.get_num_states(),
+                        oracle.get_membership_queries(),
+                        oracle.get_equivalence_queries(),
+                        if learned.is_isomorphic(oracle.get_target()) { "yes" } else { "no" }
+                    ));
+                    self.lstar_visualizer.set_steps(
+                        steps
+                            .iter()
+                            .filter_map(|step| {
+                                let graph: Graph = step.hypothesis.clone()?.into();
+                                Some((step.description.clone(), graph.into()))
+                            })
+                            .collect(),
+                    );
+                    self.lstar_steps = steps;
+                    self.lstar_current_step = 0;
+                    self.is_lstar_win_open = true;
+                    self.error = None;
+                }
+                Err(e) => self.error = Some(e.to_string()),
+            }
+        }
+        if let Some(summary) = &self.lstar_summary {
+            ui.label(summary);
+        }
+        Self::visualizer_options_ui(ui, &mut self.lstar_visualizer);
+    }
+
+    fn rpni_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Automata learning (RPNI)");
+        ui.label("samples, one for each line: + word or - word");
+        ui.text_edit_multiline(&mut self.rpni_samples_text);
+
+        // the file system is only available on native
+        #[cfg(not(target_arch = "wasm32"))]
+        ui.horizontal(|ui| {
+            ui.label("file");
+            ui.text_edit_singleline(&mut self.rpni_file_path);
+            if ui.button("Load").clicked() {
+                match std::fs::read_to_string(&self.rpni_file_path) {
+                    Ok(text) => {
+                        self.rpni_samples_text = text;
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e.to_string()),
+                }
+            }
+        });
+
+        if ui.button("Learn with RPNI").clicked() {
+            match Samples::from_text(&self.rpni_samples_text) {
+                Ok(samples) => {
+                    let (dfa, steps) = learn_rpni(&samples);
+                    self.rpni_summary = Some(format!(
+                        "inferred {} states from {} positive and {} negative samples, consistent: {}",
+                        dfa.get_num_states(),
+                        samples.positive.len(),
+                        samples.negative.len(),
+                        if samples.is_consistent(&dfa) { "yes" } else { "no" }
+                    ));
+                    self.rpni_visualizer.set_steps(
+                        steps
+                            .into_iter()
+                            .map(|(description, graph)| (description, graph.into()))
+                            .collect(),
+                    );
+                    self.error = None;
+                }
+                Err(e) => self.error = Some(e.to_string()),
+            }
+        }
+        if let Some(summary) = &self.rpni_summary {
+            ui.label(summary);
+        }
+        Self::visualizer_options_ui(ui, &mut self.rpni_visualizer);
+    }
+
+    fn grammar_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Context free grammars");
+        ui.label("one rule for each line, like E -> E + T | T");
+        ui.add(egui::TextEdit::multiline(&mut self.grammar_text).code_editor());
+
+        if ui.button("Analyze").clicked() {
+            match Grammar::from_text(&self.grammar_text) {
+                Ok(grammar) => {
+                    self.set_grammar(grammar);
+                    self.error = None;
+                }
+                Err(e) => self.error = Some(e.to_string()),
+            }
+        }
+
+        let grammar = match &self.grammar {
+            Some(grammar) => grammar,
+            None => return,
+        };
+        ui.label(format!("nullable: {}", self.grammar_nullable));
+        egui::Grid::new("grammar_sets").striped(true).show(ui, |ui| {
+            ui.label(RichText::new("").strong());
+            ui.label(RichText::new("FIRST").strong());
+            ui.label(RichText::new("FOLLOW").strong());
+            ui.end_row();
+            for (non_terminal, first, follow) in self.grammar_sets.iter() {
+                ui.label(RichText::new(non_terminal).monospace());
+                ui.label(RichText::new(first).monospace());
+                ui.label(RichText::new(follow).monospace());
+                ui.end_row();
+            }
+        });
+
+        let mut transformed = None;
+        let mut build_ll1 = false;
+        ui.horizontal_wrapped(|ui| {
+            for transformation in GrammarTransformation::ALL {
+                if ui.button(transformation.name()).clicked() {
+                    transformed = Some(transformation);
+                }
+            }
+            build_ll1 = ui.button("LL(1) table").clicked();
+            if ui.button("LR(0) automaton").clicked() {
+                let automaton = grammar.get_lr0_automaton();
+                let graph: Graph = (&automaton).into();
+                self.lr0_visualizer.set_graph(graph.into());
+            }
+        });
+        if let Some(table) = &self.ll1_table {
+            if table.is_ll1() {
+                ui.label("the grammar is LL(1)");
+            } else {
+                ui.label(
+                    RichText::new(format!(
+                        "the grammar is not LL(1), {} conflicting cells",
+                        table.get_conflicts().len()
+                    ))
+                        .color(Color32::RED),
+                );
+            }
+        }
+        if let Some(transformation) = transformed {
+            let mut after = grammar.clone();
+            let after_text = match transformation {
+                GrammarTransformation::RemoveEpsilon => {
+                    after.remove_epsilon();
+                    after.to_string()
+                }
+                GrammarTransformation::RemoveUseless => {
+                    after.remove_useless();
+                    after.to_string()
+                }
+                GrammarTransformation::RemoveUnitaryCycles => {
+                    after.remove_unitary_cycles();
+                    after.to_string()
+                }
+                GrammarTransformation::Itemization => grammar
+                    .get_itemization()
+                    .iter()
+                    .map(|item| grammar.item_to_string(item))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            };
+            self.grammar_transformation = Some((transformation.name().to_string(), grammar.to_string(), after_text));
+            self.is_grammar_win_open = true;
+        }
+        if build_ll1 {
+            self.ll1_table = self.grammar.as_mut().map(|grammar| grammar.get_ll1_table());
+            self.is_ll1_win_open = true;
+        }
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_source("lr_kind")
+                .selected_text(self.lr_kind.name())
+                .show_ui(ui, |ui| {
+                    for kind in LRKind::ALL {
+                        ui.selectable_value(&mut self.lr_kind, kind, kind.name());
+                    }
+                });
+            if ui.button("LR table").clicked() {
+                if let Some(grammar) = &self.grammar {
+                    let mut counts = vec![];
+                    for kind in LRKind::ALL {
+                        let table = grammar.get_lr_table(kind);
+                        counts.push(format!("{} {}", kind.name(), table.get_num_states()));
+                        if kind == self.lr_kind {
+                            self.lr_table = Some(table);
+                        }
+                    }
+                    self.lr_state_counts = Some(format!("states: {}", counts.join(", ")));
+                    self.is_lr_win_open = true;
+                }
+            }
+        });
+        if let Some(table) = &self.lr_table {
+            if table.has_conflicts() {
+                ui.label(
+                    RichText::new(format!(
+                        "the grammar is not {}, {} conflicting cells",
+                        table.get_kind().name(),
+                        table.get_conflicts().len()
+                    ))
+                    .color(Color32::RED),
+                );
+            } else {
+                ui.label(format!("the grammar is {}", table.get_kind().name()));
+            }
+        }
+        if let Some(counts) = &self.lr_state_counts {
+            ui.label(counts);
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("input");
+            ui.text_edit_singleline(&mut self.parse_input)
+                .on_hover_text("Terminals of the grammar separated by spaces");
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Parse with LL(1)").clicked() {
+                let result = self.parse_ll1();
+                self.show_parse("LL(1) parser", result);
+            }
+            if ui.button("Parse with SLR(1)").clicked() {
+                let result = self.parse_slr();
+                self.show_parse("SLR(1) parser", result);
+            }
+        });
+    }
+
+    fn lr_table_window(&mut self, ctx: &egui::Context) {
+        let table = match &self.lr_table {
+            Some(table) => table,
+            None => return,
+        };
+        let grammar = table.get_grammar();
+
+        Window::new(format!("{} table", table.get_kind().name()))
+            .id(egui::Id::new("lr_table"))
+            .open(&mut self.is_lr_win_open)
+            .scroll2([true, true])
+            .show(ctx, |ui| {
+                ui.label("hover a state to see its items, the productions are numbered from 0:");
+                for (index, _) in grammar.get_productions().iter().enumerate() {
+                    ui.label(RichText::new(format!("{}: {}", index, grammar.production_to_string(index))).monospace());
+                }
+
+                egui::Grid::new("lr_table_grid").striped(true).show(ui, |ui| {
+                    ui.label(RichText::new("state").strong());
+                    for terminal in table.get_terminals() {
+                        ui.label(RichText::new(grammar.terminal_to_string(terminal)).strong());
+                    }
+                    for non_terminal in table.get_non_terminals() {
+                        ui.label(RichText::new(grammar.get_symbols().get_non_terminal_name(non_terminal)).strong());
+                    }
+                    ui.end_row();
+
+                    for state in 0..table.get_num_states() {
+                        ui.label(RichText::new(state.to_string()).strong())
+                            .on_hover_text(RichText::new(table.state_to_string(state)).monospace());
+                        for terminal in table.get_terminals() {
+                            let actions = table.get_actions(state, terminal);
+                            let text = actions
+                                .iter()
+                                .map(|action| action.to_string())
+                                .collect::<Vec<String>>()
+                                .join("/");
+                            let text = RichText::new(text).monospace();
+                            ui.label(if actions.len() > 1 { text.color(Color32::RED) } else { text });
+                        }
+                        for non_terminal in table.get_non_terminals() {
+                            let goto = table.get_goto(state, non_terminal);
+                            ui.label(goto.map(|to| to.to_string()).unwrap_or_default());
+                        }
+                        ui.end_row();
+                    }
+                });
+
+                for conflict in table.get_conflicts() {
+                    let kind = if conflict.is_shift_reduce() { "shift/reduce" } else { "reduce/reduce" };
+                    ui.label(
+                        RichText::new(format!(
+                            "{} conflict in state {} on {}: {}",
+                            kind,
+                            conflict.state,
+                            grammar.terminal_to_string(conflict.terminal),
+                            conflict.items.join(", ")
+                        ))
+                        .color(Color32::RED),
+                    );
+                }
+            });
+    }
+
+    fn parse_ll1(&mut self) -> Result<ParseResult, Box<dyn Error>> {
+        let grammar = self.grammar.as_mut().ok_or("analyze a grammar before parsing")?;
+        let table = grammar.get_ll1_table();
+        let input = grammar.tokenize(&self.parse_input)?;
+        table.parse(grammar, &input)
+    }
+
+    fn parse_slr(&self) -> Result<ParseResult, Box<dyn Error>> {
+        let grammar = self.grammar.as_ref().ok_or("analyze a grammar before parsing")?;
+        let input = grammar.tokenize(&self.parse_input)?;
+        grammar.get_lr_table(LRKind::SLR1).parse(&input)
+    }
+
+    /// shows the trace of a parser in a window and its parse tree in another one
+    fn show_parse(&mut self, title: &str, result: Result<ParseResult, Box<dyn Error>>) {
+        match result {
+            Ok(result) => {
+                self.parse_steps = result.steps;
+                self.parse_errors = result.errors;
+                self.parse_title = title.to_string();
+                self.is_parse_win_open = true;
+                self.parse_tree_visualizer.set_graph(result.tree.into());
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    fn parse_trace_window(&mut self, ctx: &egui::Context) {
+        let steps = &self.parse_steps;
+        let errors = &self.parse_errors;
+        Window::new(&self.parse_title)
+            .id(egui::Id::new("parse_trace"))
+            .open(&mut self.is_parse_win_open)
+            .scroll2([true, true])
+            .show(ctx, |ui| {
+                // the LR parsers have a stack of states and one of symbols
+                let has_symbols = steps.iter().any(|step| step.symbols.is_some());
+                egui::Grid::new("parse_trace_grid").striped(true).show(ui, |ui| {
+                    ui.label(RichText::new(if has_symbols { "states" } else { "stack" }).strong());
+                    if has_symbols {
+                        ui.label(RichText::new("symbols").strong());
+                    }
+                    ui.label(RichText::new("input").strong());
+                    ui.label(RichText::new("action").strong());
+                    ui.end_row();
+                    for step in steps.iter() {
+                        ui.label(RichText::new(&step.stack).monospace());
+                        if let Some(symbols) = &step.symbols {
+                            ui.label(RichText::new(symbols).monospace());
+                        }
+                        ui.label(RichText::new(&step.input).monospace());
+                        let action = RichText::new(&step.action).monospace();
+                        ui.label(if step.action.starts_with("error") { action.color(Color32::RED) } else { action });
+                        ui.end_row();
+                    }
+                });
+
+                if errors.is_empty() {
+                    ui.label(RichText::new("accepted").color(Color32::GREEN));
+                } else {
+                    ui.label(RichText::new(format!("{} syntax errors", errors.len())).color(Color32::RED));
+                    for error in errors.iter() {
+                        ui.label(error);
+                    }
+                }
+            });
+    }
+
+    fn ll1_table_window(&mut self, ctx: &egui::Context) {
+        let (grammar, table) = match (&self.grammar, &self.ll1_table) {
+            (Some(grammar), Some(table)) => (grammar, table),
+            _ => return,
+        };
+
+        Window::new("LL(1) table")
+            .open(&mut self.is_ll1_win_open)
+            .scroll2([true, true])
+            .show(ctx, |ui| {
+                egui::Grid::new("ll1_table").striped(true).show(ui, |ui| {
+                    ui.label("");
+                    for terminal in table.get_terminals() {
+                        ui.label(RichText::new(grammar.terminal_to_string(*terminal)).strong());
+                    }
+                    ui.end_row();
+
+                    for non_terminal in table.get_non_terminals() {
+                        ui.label(RichText::new(grammar.get_symbols().get_non_terminal_name(*non_terminal)).strong());
+                        for terminal in table.get_terminals() {
+                            let productions = table.get_productions(*non_terminal, *terminal);
+                            let text = productions
+                                .iter()
+                                .map(|production| grammar.production_to_string(*production))
+                                .collect::<Vec<String>>()
+                                .join("\n");
+                            let text = RichText::new(text).monospace();
+                            ui.label(if productions.len() > 1 { text.color(Color32::RED) } else { text });
+                        }
+                        ui.end_row();
+                    }
+                });
+
+                for (non_terminal, terminal, productions) in table.get_conflicts() {
+                    let productions: Vec<String> = productions
+                        .iter()
+                        .map(|production| grammar.production_to_string(*production))
+                        .collect();
+                    ui.label(
+                        RichText::new(format!(
+                            "conflict in M[{}, {}]: {}",
+                            grammar.get_symbols().get_non_terminal_name(non_terminal),
+                            grammar.terminal_to_string(terminal),
+                            productions.join(" / ")
+                        ))
+                        .color(Color32::RED),
+                    );
+                }
+            });
+    }
+
+    /// shows the symbols as {a, b}
+    fn show_symbols(symbols: impl Iterator<Item = String>) -> String {
+        format!("{{{}}}", symbols.collect::<Vec<String>>().join(", "))
+    }
+
+    /// replaces the grammar of the editor, and computes its nullable, FIRST and FOLLOW sets
+    fn set_grammar(&mut self, mut grammar: Grammar) {
+        let symbols = grammar.get_symbols().clone();
+        self.grammar_nullable = Self::show_symbols(
+            grammar
+                .get_nullable()
+                .into_iter()
+                .map(|non_terminal| symbols.get_non_terminal_name(non_terminal).to_string()),
+        );
+
+        let first_table = grammar.get_first_table().clone();
+        let follow_table = grammar.get_follow_table().clone();
+        let show_terminals = |terminals: &BTreeSet<Terminal>| {
+            Self::show_symbols(terminals.iter().map(|terminal| grammar.terminal_to_string(*terminal)))
+        };
+        self.grammar_sets = first_table
+            .iter()
+            .zip(follow_table.values())
+            .map(|((non_terminal, first), follow)| {
+                (
+                    symbols.get_non_terminal_name(*non_terminal).to_string(),
+                    show_terminals(first),
+                    show_terminals(follow),
+                )
+            })
+            .collect();
+        self.grammar = Some(grammar);
+        self.grammar_transformation = None;
+        self.ll1_table = None;
+        self.lr_table = None;
+        self.lr_state_counts = None;
+    }
+
+    fn grammar_transformation_window(&mut self, ctx: &egui::Context) {
+        let (name, before, after) = match &self.grammar_transformation {
+            Some(transformation) => transformation,
+            None => return,
+        };
+
+        let mut use_result = false;
+        Window::new("Grammar transformation")
+            .open(&mut self.is_grammar_win_open)
+            .scroll2([true, true])
+            .show(ctx, |ui| {
+                ui.heading(name);
+                ui.columns(2, |columns| {
+                    columns[0].label(RichText::new("before").strong());
+                    columns[0].label(RichText::new(before).monospace());
+                    columns[1].label(RichText::new("after").strong());
+                    columns[1].label(RichText::new(after).monospace());
+                });
+                // the items are not a grammar
+                if name != GrammarTransformation::Itemization.name() && ui.button("Use the result").clicked() {
+                    use_result = true;
+                }
+            });
+
+        if use_result {
+            self.grammar_text = after.clone();
+            match Grammar::from_text(&self.grammar_text) {
+                Ok(grammar) => self.set_grammar(grammar),
+                Err(e) => self.error = Some(e.to_string()),
+            }
+        }
+    }
+
+    fn lstar_table_window(&mut self, ctx: &egui::Context) {
+        let steps = &self.lstar_steps;
+        let current_step = &mut self.lstar_current_step;
+        Window::new("L* observation table")
+            .open(&mut self.is_lstar_win_open)
+            .scroll2([true, true])
+            .show(ctx, |ui| {
+                if steps.is_empty() {
+                    return;
+                }
+                ui.add(egui::Slider::new(current_step, 0..=steps.len() - 1).text("step"));
+                let step = &steps[*current_step];
+                ui.label(&step.description);
+                ui.label(format!(
+                    "membership queries so far: {}",
+                    step.table.get_num_membership_queries()
+                ));
+
+                let table = &step.table;
+                egui::Grid::new("lstar_table").striped(true).show(ui, |ui| {
+                    ui.label("");
+                    for suffix in table.get_suffixes() {
+                        ui.label(RichText::new(word_to_string(suffix)).strong());
+                    }
+                    ui.end_row();
+
+                    // the rows of S first, then the ones of S·Σ
+                    let prefixes = table.get_prefixes().iter().map(|prefix| (prefix.clone(), true));
+                    let extended = table.get_extended_prefixes().into_iter().map(|prefix| (prefix, false));
+                    for (prefix, in_prefixes) in prefixes.chain(extended) {
+                        let label = if in_prefixes {
+                            RichText::new(word_to_string(&prefix)).strong()
+                        } else {
+                            RichText::new(word_to_string(&prefix)).italics()
+                        };
+                        ui.label(label);
+                        for entry in table.row(&prefix) {
+                            ui.label(if entry { "1" } else { "0" });
+                        }
+                        ui.end_row();
+                    }
+                });
+                ui.label("bold rows are in S, italic rows are in S·Σ");
+            });
+    }
+
+    /// shows the result of an operation in a new window
+    fn open_operation_window(&mut self, name: &str, result: Result<Graph, Box<dyn Error>>) {
+        match result {
+            Ok(graph) => {
+                // the counter keeps the window titles unique
+                self.num_operations += 1;
+                let mut visualizer = Visualizer::new(format!("{} #{}", name, self.num_operations));
+                visualizer.set_graph(graph.into());
+                self.operation_windows.push(visualizer);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    /// reads the mapping table, every row must map a single character
+    fn get_homomorphism(&self) -> Result<Homomorphism, Box<dyn Error>> {
+        let mut homomorphism = Homomorphism::new();
+        for (symbol, image) in self.homomorphism_rows.iter() {
+            let mut chars = symbol.trim().chars();
+            match (chars.next(), chars.next()) {
+                (Some(ch), None) if !homomorphism.contains_key(&ch) => {
+                    homomorphism.insert(ch, image.trim().to_string());
+                }
+                _ => return Err(Box::new(InvalidHomomorphism::new(symbol.clone()))),
+            }
+        }
+        Ok(homomorphism)
+    }
+
+    fn homomorphism_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Homomorphisms");
+        ui.label("an empty image (or ε) erases the character");
+
+        let mut removed = None;
+        egui::Grid::new("homomorphism_table").show(ui, |ui| {
+            for (index, (symbol, image)) in self.homomorphism_rows.iter_mut().enumerate() {
+                ui.add(egui::TextEdit::singleline(symbol).desired_width(20.));
+                ui.label("→");
+                ui.add(egui::TextEdit::singleline(image).desired_width(80.));
+                if ui.button("remove").clicked() {
+                    removed = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(index) = removed {
+            self.homomorphism_rows.remove(index);
+        }
+        if ui.button("add mapping").clicked() {
+            self.homomorphism_rows.push((String::new(), String::new()));
+        }
+
+        if self.automaton.is_none() {
+            ui.label("generate an automaton to apply the homomorphism");
+            return;
+        }
+        let (mut image_clicked, mut inverse_clicked) = (false, false);
+        ui.horizontal(|ui| {
+            image_clicked = ui
+                .button("Image h(L)")
+                .on_hover_text("the characters without a mapping are left unchanged")
+                .clicked();
+            inverse_clicked = ui
+                .button("Inverse image h⁻¹(L)")
+                .on_hover_text("the alphabet is the set of mapped characters")
+                .clicked();
+        });
+
+        if image_clicked {
+            let result = self.apply_homomorphism(false);
+            self.open_operation_window("Homomorphic image", result);
+        }
+        if inverse_clicked {
+            let result = self.apply_homomorphism(true);
+            self.open_operation_window("Inverse homomorphic image", result);
+        }
+    }
+
+    /// applies the homomorphism of the mapping table (or its inverse) to the current automaton
+    fn apply_homomorphism(&self, inverse: bool) -> Result<Graph, Box<dyn Error>> {
+        let dfa = match &self.automaton {
+            Some(dfa) => dfa,
+            None => return Err("generate an automaton before applying a homomorphism".into()),
+        };
+        let homomorphism = self.get_homomorphism()?;
+
+        if inverse {
+            Ok(dfa.inverse_homomorphism(&homomorphism).remove_unreachable().into())
+        } else {
+            Ok(NFA::from_dfa(dfa)
+                .homomorphism(&homomorphism)
+                .remove_useless_states()
+                .into())
+        }
+    }
+
+    fn operations_menu_ui(&mut self, ui: &mut egui::Ui) {
+        ui.menu_button("Operations", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("word");
+                ui.text_edit_singleline(&mut self.operation_word)
+                    .on_hover_text("Word of the quotients by word");
+            });
+            ui.horizontal(|ui| {
+                ui.label("regex");
+                ui.text_edit_singleline(&mut self.operation_regex_text)
+                    .on_hover_text("Language of the quotients by regex, of the shuffle, of the union and of the concatenation");
+            });
+            ui.separator();
+
+            for operation in Operation::ALL {
+                if ui.button(operation.name()).clicked() {
+                    let result = self.apply_operation(operation);
+                    self.open_operation_window(operation.name(), result);
+                    ui.close_menu();
+                }
+            }
+        });
+    }
+
+    fn arden_steps_window(&mut self, ctx: &egui::Context) {
+        let steps = &self.arden_steps;
+        Window::new("Arden's lemma steps")
+            .open(&mut self.is_arden_win_open)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for (index, step) in steps.iter().enumerate() {
+                        ui.label(RichText::new(format!("{}. {}", index + 1, step)).monospace());
+                        ui.separator();
+                    }
+                });
+            });
+    }
 }
 
 impl eframe::App for EguiApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::TopBottomPanel::top("Menu").show(ctx, |ui| {
+            egui::menu::bar(ui, |ui| {
+                self.operations_menu_ui(ui);
+            });
+        });
         egui::SidePanel::left("Main").show(ctx, |ui| {
-            for (index, visualizer) in self.to_visualize.iter_mut().enumerate() {
-                ui.heading(&visualizer.box_title);
-                if index == 0 {
-                    ui.horizontal(|ui| {
-                        ui.label("inserisci la regex");
-                        ui.text_edit_singleline(&mut self.regex_text)
-                            .on_hover_text("Enter a regular expression");
-                    });
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                // the regex of the generated graph, it's used to update the automaton
+                let mut generated = None;
+                for (index, visualizer) in self.to_visualize.iter_mut().enumerate() {
+                    ui.heading(&visualizer.box_title);
+                    if index == 0 {
+                        ui.horizontal(|ui| {
+                            ui.label("inserisci la regex");
+                            ui.text_edit_singleline(&mut self.regex_text)
+                                .on_hover_text("Enter a regular expression");
+                        });
+                        ui.checkbox(&mut self.hide_dead_states, "hide dead states")
+                            .on_hover_text("Hide the states of the DFAs that can't reach a final state");
+                    }
+                    if ui
+                        .button(format!("Generate {}", visualizer.box_title))
+                        .clicked()
+                    {
+                        match ReOperator::from_string(&self.regex_text) {
+                            Ok(re) => {
+                                let converter = Self::get_converter(index as i32, self.hide_dead_states);
+                                visualizer.set_graph(converter(re.clone()).into());
+                                generated = Some(re);
+                                self.error = None;
+                            }
+
+                            Err(e) => {
+                                self.error = Some(e.to_string());
+                            }
+                        };
+                    }
+
+                    Self::visualizer_options_ui(ui, visualizer);
                 }
+
+                ui.heading(&self.brzozowski_visualizer.box_title);
                 if ui
-                    .button(format!("Generate {}", visualizer.box_title))
+                    .button(format!("Generate {}", self.brzozowski_visualizer.box_title))
+                    .on_hover_text("determinize(reverse(determinize(reverse(NFA))))")
                     .clicked()
                 {
                     match ReOperator::from_string(&self.regex_text) {
                         Ok(re) => {
-                            visualizer.set_graph(Self::get_converter(index as i32)(re).into());
+                            let nfa = NFA::from(&re);
+                            let (minimized, steps) = DFA::brzozowski_minimization_with_steps(&nfa);
+                            self.brzozowski_is_isomorphic =
+                                Some(minimized.is_isomorphic(&DFA::from(&nfa).get_minimized_dfa()));
+                            self.brzozowski_visualizer.set_steps(
+                                steps
+                                    .into_iter()
+                                    .map(|(description, graph)| (description, graph.into()))
+                                    .collect(),
+                            );
+                            generated = Some(re);
                             self.error = None;
                         }
-
                         Err(e) => {
                             self.error = Some(e.to_string());
                         }
                     };
                 }
+                if let Some(is_isomorphic) = self.brzozowski_is_isomorphic {
+                    ui.label(format!(
+                        "isomorphic to the Minimized DFA: {}",
+                        if is_isomorphic { "yes" } else { "no" }
+                    ));
+                }
+                Self::visualizer_options_ui(ui, &mut self.brzozowski_visualizer);
 
-                ui.collapsing(
-                    format!("{} visualizer option", visualizer.box_title),
-                    |ui| {
-                        ui.add(
-                            egui::Slider::new(&mut visualizer.padding_x, 10.0..=100.0)
-                                .text("padding x"),
-                        );
-                        ui.add(
-                            egui::Slider::new(&mut visualizer.padding_y, 10.0..=100.0)
-                                .text("padding y"),
-                        );
-                        ui.add(
-                            egui::Slider::new(&mut visualizer.size_node, 10.0..=100.0)
-                                .text("node size"),
-                        );
-                    },
-                );
-            }
-            if let Some(err) = &self.error {
-                ui.label(RichText::new(err).color(Color32::RED));
-            }
-        });
-        for visualizer in self.to_visualize.iter_mut() {
-            visualizer.check_open();
-            let syntaxTree = Window::new(format!("{}", visualizer.box_title));
-            let syntaxTree = syntaxTree.open(&mut visualizer.is_win_open);
-            let syntaxTree = syntaxTree.scroll2([true, true]);
-            syntaxTree.show(ctx, |ui| {
-                Frame::canvas(ui.style()).show(ui, |ui| {
-                    if let Some(tree) = &mut visualizer.graph {
-                        let scren_size = tree.position(DisplayGraphParameter {
-                            padding_x: visualizer.padding_x,
-                            padding_y: visualizer.padding_y,
-                            node_size: visualizer.size_node,
-                        });
-                        let (mut response, painter) =
-                            ui.allocate_painter(scren_size, egui::Sense::hover());
+                if let Some(re) = generated {
+                    self.set_automaton(&re);
+                }
+                if let Some(err) = &self.error {
+                    ui.label(RichText::new(err).color(Color32::RED));
+                }
 
-                        let to_screen = emath::RectTransform::from_to(
-                            Rect::from_min_size(Pos2::ZERO, response.rect.size()),
-                            response.rect,
-                        );
-                        tree.drag_nodes(to_screen, ui, &mut response);
-                        tree.draw(&painter, to_screen, &ui);
-                    }
-                })
+                ui.separator();
+                self.language_properties_ui(ui);
+                ui.separator();
+                self.accepted_words_ui(ui);
+                ui.separator();
+                self.myhill_nerode_ui(ui);
+                ui.separator();
+                self.state_elimination_ui(ui);
+                ui.separator();
+                self.homomorphism_ui(ui);
+                ui.separator();
+                self.pumping_lemma_ui(ui);
+                ui.separator();
+                self.lstar_ui(ui);
+                ui.separator();
+                self.rpni_ui(ui);
+                ui.separator();
+                self.grammar_ui(ui);
             });
+        });
+        for visualizer in self.to_visualize.iter_mut() {
+            visualizer.show(ctx);
+        }
+        self.brzozowski_visualizer.show(ctx);
+        self.elimination_visualizer.show(ctx);
+        self.arden_steps_window(ctx);
+        self.pumping_visualizer.show(ctx);
+        self.lstar_visualizer.show(ctx);
+        self.lstar_table_window(ctx);
+        self.rpni_visualizer.show(ctx);
+        self.grammar_transformation_window(ctx);
+        self.ll1_table_window(ctx);
+        self.parse_trace_window(ctx);
+        self.parse_tree_visualizer.show(ctx);
+        self.lr0_visualizer.show(ctx);
+        self.lr_table_window(ctx);
+        for visualizer in self.operation_windows.iter_mut() {
+            visualizer.show(ctx);
         }
+        self.operation_windows.retain(|visualizer| visualizer.is_win_open);
     }
 }
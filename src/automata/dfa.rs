@@ -1,11 +1,15 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
 use crate::automata::NFA;
 use crate::automata::regular_expression as RE;
+use crate::automata::reachability::{co_reachable_from, reachable_from};
 use crate::utils::{Graph, IndEdge, IndNode};
 use crate::utils::DisjointUnionFind;
 
-type NfaStates = BTreeSet<usize>;
+pub type NfaStates = BTreeSet<usize>;
+/// state of the product of two DFAs, None is the implicit dead state
+type PairState = (usize, Option<usize>);
 
 #[derive(Debug, Clone)]
 pub struct DFA<T> {
@@ -78,7 +82,297 @@ impl<T> DFA<T> {
         &self.transitions
     }
 
+    pub fn get_num_states(&self) -> usize {
+        self.num_states
+    }
+
+    pub fn get_alphabet(&self) -> &Vec<char> {
+        &self.alphabet
+    }
+
+    /// returns the state reached from `state` reading `input`, None if the
+    /// transition is missing (it goes to an implicit dead state)
+    pub fn get_next_state(&self, state: usize, input: char) -> Option<usize> {
+        self.transitions[state].get(&input).copied()
+    }
+
+    /// runs the DFA on the word, a missing transition rejects the word
+    pub fn accepts(&self, word: &str) -> bool {
+        let mut state = self.start_state;
+        for ch in word.chars() {
+            match self.get_next_state(state, ch) {
+                Some(next) => state = next,
+                None => return false,
+            }
+        }
+
+        self.is_final_state(state)
+    }
+
+    /// returns the successors of each state, whatever the character
+    fn get_successors(&self) -> Vec<Vec<usize>> {
+        self.transitions
+            .iter()
+            .map(|adj| adj.values().cloned().collect())
+            .collect()
+    }
+
+    /// returns the states that can be reached from the start state
+    pub fn get_reachable_states(&self) -> BTreeSet<usize> {
+        reachable_from([self.start_state], &self.get_successors())
+    }
+
+    /// returns the states from which a final state can be reached
+    pub fn get_live_states(&self) -> BTreeSet<usize> {
+        co_reachable_from(self.end_states.iter().cloned(), &self.get_successors())
+    }
+
+    /// states that are both reachable and live, these are the only states
+    /// that take part in accepting runs
+    pub fn get_useful_states(&self) -> BTreeSet<usize> {
+        let live = self.get_live_states();
+        self.get_reachable_states()
+            .into_iter()
+            .filter(|state| live.contains(state))
+            .collect()
+    }
+
+    /// the language is empty when no final state is reachable
+    pub fn is_empty(&self) -> bool {
+        self.get_reachable_states()
+            .iter()
+            .all(|state| !self.is_final_state(*state))
+    }
+
+    /// the language is universal (over the alphabet of the DFA) when every
+    /// reachable state is final and has a transition for every character
+    pub fn is_universal(&self) -> bool {
+        self.get_reachable_states().iter().all(|state| {
+            self.is_final_state(*state)
+                && self
+                    .alphabet
+                    .iter()
+                    .all(|ch| self.transitions[*state].contains_key(ch))
+        })
+    }
+
+    /// the language is finite when there are no cycles among the useful states
+    pub fn is_finite(&self) -> bool {
+        let useful = self.get_useful_states();
+
+        // 0: not visited, 1: in the current dfs path, 2: done
+        let mut color = vec![0; self.num_states];
+        for state in useful.iter() {
+            if color[*state] == 0 && self.has_useful_cycle(*state, &useful, &mut color) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn has_useful_cycle(&self, state: usize, useful: &BTreeSet<usize>, color: &mut [u8]) -> bool {
+        color[state] = 1;
+        for next in self.transitions[state].values() {
+            if !useful.contains(next) {
+                continue;
+            }
+            if color[*next] == 1 {
+                return true;
+            }
+            if color[*next] == 0 && self.has_useful_cycle(*next, useful, color) {
+                return true;
+            }
+        }
+        color[state] = 2;
+
+        false
+    }
+
+    /// returns the number of words in the language, None if it is infinite
+    pub fn cardinality(&self) -> Option<usize> {
+        if !self.is_finite() {
+            return None;
+        }
+
+        let useful = self.get_useful_states();
+        if !useful.contains(&self.start_state) {
+            return Some(0);
+        }
+
+        let mut memo = vec![None; self.num_states];
+        Some(self.count_paths(self.start_state, &useful, &mut memo))
+    }
+
+    /// counts the accepted words starting from `state`, the useful part of the
+    /// DFA must be acyclic
+    fn count_paths(&self, state: usize, useful: &BTreeSet<usize>, memo: &mut [Option<usize>]) -> usize {
+        if let Some(count) = memo[state] {
+            return count;
+        }
+
+        let mut count: usize = if self.is_final_state(state) { 1 } else { 0 };
+        for next in self.transitions[state].values() {
+            if useful.contains(next) {
+                count = count.saturating_add(self.count_paths(*next, useful, memo));
+            }
+        }
+        memo[state] = Some(count);
+
+        count
+    }
+
+    pub fn is_subset_of<U>(&self, other: &DFA<U>) -> bool {
+        self.get_subset_witness(other).is_none()
+    }
+
+    /// returns the shortest (and lexicographically smallest) word accepted by
+    /// this DFA but not by `other`, None if the language is a subset of the other.
+    ///
+    /// It explores the product automaton, a None state in the pair is the
+    /// implicit dead state of a missing transition.
+    pub fn get_subset_witness<U>(&self, other: &DFA<U>) -> Option<String> {
+        let alphabet: BTreeSet<char> = self
+            .alphabet
+            .iter()
+            .chain(other.get_alphabet().iter())
+            .cloned()
+            .collect();
+
+        let start = (self.start_state, Some(other.get_start_state()));
+        let mut parent: BTreeMap<PairState, Option<(PairState, char)>> = BTreeMap::new();
+        parent.insert(start, None);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(pair) = queue.pop_front() {
+            let (state, other_state) = pair;
+            let other_accepts = other_state.is_some_and(|s| other.is_final_state(s));
+            if self.is_final_state(state) && !other_accepts {
+                let mut word = vec![];
+                let mut curr = pair;
+                while let Some((prev, ch)) = parent[&curr] {
+                    word.push(ch);
+                    curr = prev;
+                }
+                return Some(word.into_iter().rev().collect());
+            }
+
+            for ch in alphabet.iter() {
+                // if this DFA rejects there is no point in going on
+                if let Some(next) = self.get_next_state(state, *ch) {
+                    let next_other = other_state.and_then(|s| other.get_next_state(s, *ch));
+                    let next_pair = (next, next_other);
+                    if let Entry::Vacant(entry) = parent.entry(next_pair) {
+                        entry.insert(Some((pair, *ch)));
+                        queue.push_back(next_pair);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// a DFA is complete when every state has a transition for every character
+    pub fn is_complete(&self) -> bool {
+        self.transitions
+            .iter()
+            .all(|adj| self.alphabet.iter().all(|ch| adj.contains_key(ch)))
+    }
+
+    /// returns an equivalent complete DFA, missing transitions go to a new sink state.
+    /// The data of the states is not kept.
+    pub fn get_complete_dfa(&self) -> Self {
+        let mut transitions = self.transitions.clone();
+        let mut num_states = self.num_states;
+
+        if !self.is_complete() {
+            let sink = num_states;
+            num_states += 1;
+            transitions.push(BTreeMap::new());
+            for adj in transitions.iter_mut() {
+                for ch in self.alphabet.iter() {
+                    adj.entry(*ch).or_insert(sink);
+                }
+            }
+        }
+
+        Self {
+            num_states,
+            start_state: self.start_state,
+            end_states: self.end_states.clone(),
+            transitions,
+            alphabet: self.alphabet.clone(),
+            idx_to_data: None,
+        }
+    }
+
+    /// returns the DFA without the states that can't be reached from the start state
+    pub fn remove_unreachable(self) -> Self {
+        let reachable = self.get_reachable_states();
+        self.retain_states(&reachable)
+    }
+
+    /// returns the DFA without dead states, from which no final state can be reached
+    /// (like the empty set state of the subset construction).
+    /// The start state is always kept, the result can be a partial DFA.
+    pub fn remove_dead(self) -> Self {
+        let mut live = self.get_live_states();
+        live.insert(self.start_state);
+        self.retain_states(&live)
+    }
+
+    /// returns the DFA with only the useful states, that are reachable and live.
+    /// The start state is always kept, the result can be a partial DFA.
+    pub fn trim(self) -> Self {
+        self.remove_unreachable().remove_dead()
+    }
+
+    /// keeps only the given states, renumbered in increasing order,
+    /// the transitions to the removed states are dropped
+    fn retain_states(self, states: &BTreeSet<usize>) -> Self {
+        let old_to_new: BTreeMap<usize, usize> = states
+            .iter()
+            .enumerate()
+            .map(|(new, old)| (*old, new))
+            .collect();
+
+        let transitions = states
+            .iter()
+            .map(|old| {
+                self.transitions[*old]
+                    .iter()
+                    .filter_map(|(ch, to)| old_to_new.get(to).map(|to| (*ch, *to)))
+                    .collect()
+            })
+            .collect();
+        let end_states = self
+            .end_states
+            .iter()
+            .filter_map(|state| old_to_new.get(state).cloned())
+            .collect();
+        let idx_to_data = self.idx_to_data.map(|map| {
+            map.into_iter()
+                .filter_map(|(old, data)| old_to_new.get(&old).map(|new| (*new, data)))
+                .collect()
+        });
+
+        Self {
+            num_states: states.len(),
+            start_state: old_to_new[&self.start_state],
+            end_states,
+            transitions,
+            alphabet: self.alphabet,
+            idx_to_data,
+        }
+    }
+
+    /// the minimization works on complete DFAs, partial ones are completed first
     pub fn get_minimized_dfa(&self) -> Self {
+        if !self.is_complete() {
+            return self.get_complete_dfa().get_minimized_dfa();
+        }
+
         let equivalent_states = self.get_equivalent_states();
         let mut unequal_sets = DisjointUnionFind::new(self.num_states);
 
@@ -114,7 +408,7 @@ impl<T> DFA<T> {
             new_end_states.insert(*head_to_idx.get(&head).unwrap());
         }
 
-        Self {
+        let minimized: DFA<T> = Self {
             num_states,
 
             start_state: *head_to_idx.get(&unequal_sets.find(self.start_state)).unwrap(),
@@ -122,7 +416,105 @@ impl<T> DFA<T> {
             transitions: new_transitions,
             alphabet: self.alphabet.clone(),
             idx_to_data: None,
+        };
+
+        // the heads of the union find depend on the order of the joins,
+        // so the states are renumbered to be stable
+        minimized.get_canonical_dfa()
+    }
+
+    /// returns the same DFA with the states renumbered in the order they are found
+    /// by a bfs from the start state, exploring the characters in alphabetical order.
+    /// Unreachable states are numbered last, in their original order.
+    ///
+    /// Isomorphic DFAs have the same canonical numbering of their reachable states.
+    pub fn get_canonical_dfa(self) -> Self {
+        let mut alphabet = self.alphabet.clone();
+        alphabet.sort();
+
+        let mut old_to_new = vec![None; self.num_states];
+        let mut new_to_old = vec![self.start_state];
+        old_to_new[self.start_state] = Some(0);
+
+        let mut curr = 0;
+        while curr < new_to_old.len() {
+            let old = new_to_old[curr];
+            for ch in alphabet.iter() {
+                if let Some(next) = self.get_next_state(old, *ch) {
+                    if old_to_new[next].is_none() {
+                        old_to_new[next] = Some(new_to_old.len());
+                        new_to_old.push(next);
+                    }
+                }
+            }
+            curr += 1;
+        }
+
+        for (state, new) in old_to_new.iter_mut().enumerate() {
+            if new.is_none() {
+                *new = Some(new_to_old.len());
+                new_to_old.push(state);
+            }
+        }
+        let old_to_new: Vec<usize> = old_to_new.into_iter().map(|new| new.unwrap()).collect();
+
+        let transitions = new_to_old
+            .iter()
+            .map(|old| {
+                self.transitions[*old]
+                    .iter()
+                    .map(|(ch, to)| (*ch, old_to_new[*to]))
+                    .collect()
+            })
+            .collect();
+        let mut end_states: Vec<usize> = self.end_states.iter().map(|old| old_to_new[*old]).collect();
+        end_states.sort();
+        let idx_to_data = self.idx_to_data.map(|map| {
+            map.into_iter()
+                .map(|(old, data)| (old_to_new[old], data))
+                .collect()
+        });
+
+        Self {
+            num_states: self.num_states,
+            start_state: 0,
+            end_states,
+            transitions,
+            alphabet: self.alphabet,
+            idx_to_data,
+        }
+    }
+
+    /// two DFAs are isomorphic when there's a bijection between their reachable states
+    /// that maps the start states, the final states and the transitions into each other
+    pub fn is_isomorphic<U>(&self, other: &DFA<U>) -> bool {
+        let mut to_other = BTreeMap::from([(self.start_state, other.get_start_state())]);
+        let mut from_other = BTreeMap::from([(other.get_start_state(), self.start_state)]);
+        let mut queue = vec![(self.start_state, other.get_start_state())];
+
+        while let Some((state, other_state)) = queue.pop() {
+            if self.is_final_state(state) != other.is_final_state(other_state)
+                || !self.transitions[state].keys().eq(other.get_transitions()[other_state].keys())
+            {
+                return false;
+            }
+
+            for (ch, next) in self.transitions[state].iter() {
+                let next_other = other.get_transitions()[other_state][ch];
+                match (to_other.get(next), from_other.get(&next_other)) {
+                    (Some(mapped), _) if *mapped != next_other => return false,
+                    (_, Some(mapped)) if mapped != next => return false,
+                    (Some(_), Some(_)) => {}
+                    _ => {
+                        to_other.insert(*next, next_other);
+                        from_other.insert(next_other, *next);
+                        queue.push((*next, next_other));
+                    }
+                }
+            }
         }
+
+        true
     }
 
     pub fn make_move(&self, state: usize, input: char) -> usize {
@@ -176,7 +568,11 @@ impl<T> DFA<T> {
                             std::mem::swap(&mut next_i, &mut next_j);
                         }
 
-                        if minimize_table[next_i][next_j] != INVALID_STATE {
+                        // only pairs marked in the previous iterations are used, so a pair
+                        // is marked at iteration k when its shortest distinguishing suffix
+                        // has length k
+                        let mark = minimize_table[next_i][next_j];
+                        if mark != INVALID_STATE && mark < curr_iter {
                             minimize_table[i][j] = curr_iter;
                             has_changed = true;
                             break;
@@ -191,6 +587,76 @@ impl<T> DFA<T> {
         minimize_table
     }
 
+    /// returns the shortest suffix that distinguishes each pair (i, j), with i < j,
+    /// of non equivalent states: exactly one of them accepts after reading it.
+    ///
+    /// It's derived from the minimize table, if a pair was marked at iteration k
+    /// the suffix is a character that leads to a pair marked at iteration k - 1,
+    /// followed by the suffix of that pair. The DFA must be complete.
+    pub fn get_distinguishing_suffixes(&self) -> BTreeMap<(usize, usize), String> {
+        let minimize_table = self.compute_minimize_table();
+        let mut alphabet = self.alphabet.clone();
+        alphabet.sort();
+
+        let mut marked_pairs = vec![];
+        for (i, row) in minimize_table.iter().enumerate() {
+            for (j, mark) in row.iter().enumerate().skip(i + 1) {
+                if *mark != INVALID_STATE {
+                    marked_pairs.push((*mark, i, j));
+                }
+            }
+        }
+        // the suffixes of the pairs marked before are needed first
+        marked_pairs.sort();
+
+        let mut suffixes: BTreeMap<(usize, usize), String> = BTreeMap::new();
+        for (mark, i, j) in marked_pairs {
+            if mark == 0 {
+                suffixes.insert((i, j), String::new());
+                continue;
+            }
+
+            for ch in alphabet.iter() {
+                let next_i = self.make_move(i, *ch);
+                let next_j = self.make_move(j, *ch);
+                let next_pair = (next_i.min(next_j), next_i.max(next_j));
+
+                if next_i != next_j && minimize_table[next_pair.0][next_pair.1] == mark - 1 {
+                    let suffix = format!("{}{}", ch, suffixes[&next_pair]);
+                    suffixes.insert((i, j), suffix);
+                    break;
+                }
+            }
+        }
+
+        suffixes
+    }
+
+    /// returns for each state the shortest (and lexicographically smallest) word
+    /// that leads to it from the start state, None for unreachable states
+    pub fn get_access_words(&self) -> Vec<Option<String>> {
+        let mut alphabet = self.alphabet.clone();
+        alphabet.sort();
+
+        let mut access_words = vec![None; self.num_states];
+        access_words[self.start_state] = Some(String::new());
+        let mut queue = VecDeque::from([self.start_state]);
+
+        while let Some(state) = queue.pop_front() {
+            for ch in alphabet.iter() {
+                if let Some(next) = self.get_next_state(state, *ch) {
+                    if access_words[next].is_none() {
+                        let word = format!("{}{}", access_words[state].as_ref().unwrap(), ch);
+                        access_words[next] = Some(word);
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        access_words
+    }
+
     /// @returns the initialized minimized table and vector of states to merge, with
     /// just stage-0 un-equal states marked
     fn initialize_minimize_table(&self) -> Vec<Vec<i32>> {
@@ -269,6 +735,70 @@ impl From<&NFA> for DFA<NfaStates> {
     }
 }
 
+impl DFA<NfaStates> {
+    /// subset construction where the subsets are identified by their important states:
+    /// the other states of an ε-closure have only ε transitions and are not final,
+    /// so two closures with the same important states are the same state.
+    /// Brzozowski minimization needs it, the DFA of a reversed DFA built on the
+    /// full closures can have equivalent states
+    fn determinize_important_states(nfa: &NFA) -> Self {
+        let mut dfa = DFA::new();
+        let alphabet = nfa.get_alphabet();
+        let closure = |states: BTreeSet<usize>| {
+            nfa.get_important_states(&nfa.epsilon_closure(&states.into_iter().collect()))
+        };
+
+        let mut state_to_index: BTreeMap<NfaStates, usize> = BTreeMap::new();
+        let start = closure(BTreeSet::from([nfa.get_start_state()]));
+        dfa.start_state = Self::add_state(&mut dfa, start.clone());
+        state_to_index.insert(start, dfa.start_state);
+        let mut queue = vec![dfa.start_state];
+
+        while let Some(current_state) = queue.pop() {
+            let current_set: NfaStates = dfa.idx_to_data.as_ref().unwrap()[&current_state].clone();
+            if nfa.contains_final_state(&current_set) {
+                dfa.end_states.push(current_state);
+            }
+
+            for ch in alphabet.iter() {
+                let next_set = closure(nfa.make_move(&current_set, *ch));
+                let next_state = match state_to_index.get(&next_set) {
+                    Some(next_state) => *next_state,
+                    None => {
+                        let next_state = Self::add_state(&mut dfa, next_set.clone());
+                        state_to_index.insert(next_set, next_state);
+                        queue.push(next_state);
+                        next_state
+                    }
+                };
+                dfa.transitions[current_state].insert(*ch, next_state);
+            }
+        }
+        dfa.alphabet = alphabet;
+
+        dfa
+    }
+
+    /// Brzozowski minimization: determinize(reverse(determinize(reverse(nfa)))).
+    /// returns the minimal DFA, with only reachable states, together with the graphs
+    /// of the four automata built by the algorithm (the last one is the result)
+    pub fn brzozowski_minimization_with_steps(nfa: &NFA) -> (Self, Vec<(String, Graph)>) {
+        let reversed = nfa.reverse();
+        let determinized = DFA::determinize_important_states(&reversed);
+        let reversed_again = NFA::from_dfa(&determinized).reverse();
+        let minimized = DFA::determinize_important_states(&reversed_again);
+
+        let steps = vec![
+            ("reverse(A)".to_string(), reversed.into()),
+            ("determinize(reverse(A))".to_string(), determinized.into()),
+            ("reverse(determinize(reverse(A)))".to_string(), reversed_again.into()),
+            ("determinize(reverse(determinize(reverse(A))))".to_string(), minimized.clone().into()),
+        ];
+
+        (minimized, steps)
+    }
+}
+
 impl From<&RE::ReOperator> for DFA<NfaStates> {
     fn from(regex: &RE::ReOperator) -> Self {
         let nfa = NFA::from(regex);
@@ -321,3 +851,233 @@ impl<T> Into<Graph> for DFA<T> {
         graph
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::automata::test_utils::dfa_from_regex;
+    use crate::map;
+
+    #[test]
+    fn brzozowski_minimization() {
+        for str in ["a(b|c)*", "(ab|ba)*a", "a*b*", "(a|b)*abb", "(a|b)(a|b)(a|b)", "ε|ab"] {
+            let regex = RE::ReOperator::from_string(&str.to_string()).unwrap();
+            let nfa = NFA::from(&regex);
+
+            let (brzozowski, _) = DFA::brzozowski_minimization_with_steps(&nfa);
+            let minimized = DFA::from(&nfa).get_minimized_dfa();
+
+            assert!(brzozowski.is_isomorphic(&minimized));
+
+            // the canonical numbering is the same
+            let brzozowski = brzozowski.get_canonical_dfa();
+            assert_eq!(brzozowski.transitions, minimized.transitions);
+            assert_eq!(brzozowski.end_states, minimized.end_states);
+        }
+    }
+
+    #[test]
+    fn brzozowski_steps() {
+        let regex = RE::ReOperator::from_string(&"(a|b)*abb".to_string()).unwrap();
+        let (minimized, steps) = DFA::brzozowski_minimization_with_steps(&NFA::from(&regex));
+
+        assert_eq!(steps.len(), 4);
+        assert_eq!(steps[3].1.get_nodes_ids().len(), minimized.get_num_states());
+    }
+
+    #[test]
+    fn canonical_numbering() {
+        // the same language, with different minimization orders
+        let first = dfa_from_regex("(a|b)*");
+        let second = dfa_from_regex("(a*b*)*");
+
+        assert_eq!(first.transitions, second.transitions);
+        assert_eq!(first.end_states, second.end_states);
+
+        // states are numbered in bfs order from the start
+        let dfa = dfa_from_regex("ab(c|ε)");
+        assert_eq!(dfa.start_state, 0);
+        assert_eq!(dfa.transitions[0][&'a'], 1);
+        assert_eq!(dfa.transitions[1][&'b'], 3);
+    }
+
+    #[test]
+    fn isomorphism() {
+        let dfa = dfa_from_regex("(ab|ba)*");
+
+        // swap the names of state 1 and 2
+        let swap = |state: usize| match state {
+            1 => 2,
+            2 => 1,
+            other => other,
+        };
+        let mut transitions = vec![BTreeMap::new(); dfa.num_states];
+        for (from, adj) in dfa.transitions.iter().enumerate() {
+            transitions[swap(from)] = adj.iter().map(|(ch, to)| (*ch, swap(*to))).collect();
+        }
+        let end_states = dfa.end_states.iter().map(|state| swap(*state)).collect();
+        let swapped: DFA<usize> = DFA::from_state(dfa.num_states, 0, end_states, transitions, None);
+
+        assert!(dfa.is_isomorphic(&swapped));
+        assert!(!dfa.is_isomorphic(&dfa_from_regex("(ab|ba)*a")));
+        assert!(!dfa.is_isomorphic(&dfa_from_regex("(ab|bb)*")));
+    }
+
+    #[test]
+    fn remove_dead_states() {
+        // the subset construction creates the empty set state
+        let regex = RE::ReOperator::from_string(&"ab".to_string()).unwrap();
+        let dfa = DFA::from(&regex);
+        let empty_state = dfa.idx_to_data.as_ref().unwrap().values().any(|set| set.is_empty());
+        assert!(empty_state);
+
+        let trimmed = dfa.clone().remove_dead();
+        assert!(trimmed.idx_to_data.as_ref().unwrap().values().all(|set| !set.is_empty()));
+        assert_eq!(trimmed.get_live_states().len(), trimmed.num_states);
+        assert!(!trimmed.is_complete());
+
+        assert!(trimmed.accepts("ab"));
+        assert!(!trimmed.accepts("aa"));
+    }
+
+    #[test]
+    fn remove_unreachable_states() {
+        // state 2 can't be reached, state 3 is dead
+        let dfa: DFA<usize> = DFA::from_state(
+            4,
+            0,
+            vec![1],
+            vec![
+                map! { 'a' => 1, 'b' => 3 },
+                map! { 'a' => 1, 'b' => 3 },
+                map! { 'a' => 0, 'b' => 1 },
+                map! { 'a' => 3, 'b' => 3 },
+            ],
+            None,
+        );
+
+        let reachable = dfa.clone().remove_unreachable();
+        assert_eq!(reachable.num_states, 3);
+        assert!(reachable.is_complete());
+
+        let trimmed = dfa.trim();
+        assert_eq!(trimmed.num_states, 2);
+        assert_eq!(trimmed.transitions, vec![map! { 'a' => 1 }, map! { 'a' => 1 }]);
+        assert_eq!(trimmed.end_states, vec![1]);
+    }
+
+    #[test]
+    fn minimize_partial_dfa() {
+        let regex = RE::ReOperator::from_string(&"a(b|c)*".to_string()).unwrap();
+        let dfa = DFA::from(&regex);
+        let minimized = dfa.get_minimized_dfa();
+
+        let trimmed = dfa.trim();
+        assert!(trimmed.get_minimized_dfa().is_isomorphic(&minimized));
+    }
+
+    #[test]
+    fn distinguishing_suffixes() {
+        let dfa = dfa_from_regex("(a|b)*abb");
+        let access_words = dfa.get_access_words();
+        let suffixes = dfa.get_distinguishing_suffixes();
+
+        // every pair of states of the minimized DFA is distinguishable
+        assert_eq!(suffixes.len(), dfa.num_states * (dfa.num_states - 1) / 2);
+
+        // all words over {a, b} up to length 3
+        let mut short_words = vec![String::new()];
+        for len in 0..3 {
+            for word in short_words.clone() {
+                if word.len() == len {
+                    short_words.push(format!("{}a", word));
+                    short_words.push(format!("{}b", word));
+                }
+            }
+        }
+
+        for ((i, j), suffix) in suffixes.iter() {
+            let first = access_words[*i].as_ref().unwrap();
+            let second = access_words[*j].as_ref().unwrap();
+            let distinguishes = |suffix: &String| {
+                dfa.accepts(&format!("{}{}", first, suffix))
+                    != dfa.accepts(&format!("{}{}", second, suffix))
+            };
+
+            assert!(distinguishes(suffix));
+            // and there's no shorter suffix
+            assert!(short_words
+                .iter()
+                .filter(|word| word.len() < suffix.len())
+                .all(|word| !distinguishes(word)));
+        }
+    }
+
+    #[test]
+    fn access_words() {
+        let dfa = dfa_from_regex("(a|b)*abb");
+        let access_words = dfa.get_access_words();
+
+        assert_eq!(access_words[0], Some("".to_string()));
+        assert!(access_words.contains(&Some("abb".to_string())));
+        for (state, word) in access_words.iter().enumerate() {
+            assert_eq!(dfa.is_final_state(state), dfa.accepts(word.as_ref().unwrap()));
+        }
+    }
+
+    #[test]
+    fn finite_language() {
+        let dfa = dfa_from_regex("ab|a|b(a|b)");
+
+        assert!(!dfa.is_empty());
+        assert!(dfa.is_finite());
+        assert!(!dfa.is_universal());
+        assert_eq!(dfa.cardinality(), Some(4));
+    }
+
+    #[test]
+    fn infinite_language() {
+        let dfa = dfa_from_regex("ab*");
+
+        assert!(!dfa.is_finite());
+        assert_eq!(dfa.cardinality(), None);
+    }
+
+    #[test]
+    fn universal_language() {
+        let dfa = dfa_from_regex("(a|b)*");
+        assert!(dfa.is_universal());
+
+        let dfa = dfa_from_regex("(a|b)(a|b)*");
+        assert!(!dfa.is_universal());
+    }
+
+    #[test]
+    fn empty_language() {
+        // only the start state is reachable, and it's not final
+        let dfa: DFA<usize> = DFA::from_state(2, 0, vec![1], vec![map! { 'a' => 0 }, map! {}], None);
+
+        assert!(dfa.is_empty());
+        assert!(dfa.is_finite());
+        assert_eq!(dfa.cardinality(), Some(0));
+    }
+
+    #[test]
+    fn subset_with_witness() {
+        let small = dfa_from_regex("a(ab)*");
+        let big = dfa_from_regex("a(a|b)*");
+
+        assert!(small.is_subset_of(&big));
+        assert!(!big.is_subset_of(&small));
+        assert_eq!(big.get_subset_witness(&small), Some("aa".to_string()));
+    }
+
+    #[test]
+    fn subset_with_different_alphabets() {
+        let left = dfa_from_regex("ac*");
+        let right = dfa_from_regex("a(b)*");
+
+        assert_eq!(left.get_subset_witness(&right), Some("ac".to_string()));
+        assert_eq!(right.get_subset_witness(&left), Some("ab".to_string()));
+    }
+}
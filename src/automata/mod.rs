@@ -1,7 +1,17 @@
  mod dfa;
  mod nfa;
+ mod operations;
+ mod pumping;
+ mod reachability;
  mod regular_expression;
+ mod state_elimination;
+ #[cfg(test)]
+ pub(crate) mod test_utils;
+ mod words;
 
  pub use dfa::*;
  pub use nfa::*;
- pub use regular_expression::*;
\ No newline at end of file
+ pub use operations::*;
+ pub use pumping::*;
+ pub use regular_expression::*;
+ pub use state_elimination::*;
@@ -2,10 +2,12 @@ use log::info;
 use std::collections::{BTreeMap, BTreeSet};
 
 use crate::automata::regular_expression as RE;
+use crate::automata::reachability::{co_reachable_from, reachable_from};
+use crate::automata::{Homomorphism, DFA};
 use crate::display::DisplayGraph;
 use crate::utils::Graph;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct NFA {
     start_state: usize,
     num_states: usize,
@@ -28,10 +30,225 @@ impl NFA {
         }
     }
 
+    /// Returns a NFA described by the input parameters, ε transitions use the 'ε' character.
+    /// if alphabet is None, the alphabet is inferred from the transitions.
+    pub fn from_state(
+        num_states: usize,
+        start_state: usize,
+        end_states: Vec<usize>,
+        transitions: Vec<BTreeMap<char, Vec<usize>>>,
+        alphabet: Option<Vec<char>>,
+    ) -> Self {
+        let used_alphabet = match alphabet {
+            Some(alphabet) => alphabet.into_iter().collect(),
+            None => transitions
+                .iter()
+                .flat_map(|adj| adj.keys().cloned())
+                .filter(|ch| *ch != RE::EPSILON)
+                .collect(),
+        };
+
+        Self {
+            num_states,
+            start_state,
+            end_states,
+            transitions,
+            used_alphabet,
+        }
+    }
+
+    /// returns a NFA with the same states and transitions of the DFA
+    pub fn from_dfa<T>(dfa: &DFA<T>) -> Self {
+        let transitions = dfa
+            .get_transitions()
+            .iter()
+            .map(|adj| adj.iter().map(|(ch, to)| (*ch, vec![*to])).collect())
+            .collect();
+
+        Self::from_state(
+            dfa.get_num_states(),
+            dfa.get_start_state(),
+            dfa.get_end_states().clone(),
+            transitions,
+            Some(dfa.get_alphabet().clone()),
+        )
+    }
+
+    fn add_state(&mut self) -> usize {
+        self.num_states += 1;
+        self.transitions.push(BTreeMap::new());
+        self.num_states - 1
+    }
+
+    fn add_transition(&mut self, from: usize, ch: char, to: usize) {
+        self.transitions[from].entry(ch).or_default().push(to);
+        if ch != RE::EPSILON {
+            self.used_alphabet.insert(ch);
+        }
+    }
+
+    /// copies all the states and transitions of the other NFA in this one, the states
+    /// are renumbered adding the returned offset. Start and end states are not changed.
+    fn append_states(&mut self, other: &NFA) -> usize {
+        let offset = self.num_states;
+        for adj in other.transitions.iter() {
+            self.transitions.push(
+                adj.iter()
+                    .map(|(ch, to_list)| (*ch, to_list.iter().map(|to| to + offset).collect()))
+                    .collect(),
+            );
+        }
+        self.num_states += other.num_states;
+        self.used_alphabet.extend(other.used_alphabet.iter().cloned());
+
+        offset
+    }
+
+    /// returns a NFA for the union of the two languages
+    pub fn union(&self, other: &NFA) -> Self {
+        let mut nfa = Self::new();
+        nfa.start_state = nfa.add_state();
+        let left = nfa.append_states(self);
+        let right = nfa.append_states(other);
+
+        nfa.add_transition(nfa.start_state, RE::EPSILON, self.start_state + left);
+        nfa.add_transition(nfa.start_state, RE::EPSILON, other.start_state + right);
+        nfa.end_states = self
+            .end_states
+            .iter()
+            .map(|state| state + left)
+            .chain(other.end_states.iter().map(|state| state + right))
+            .collect();
+
+        nfa
+    }
+
+    /// returns a NFA for the concatenation of the two languages
+    pub fn concat(&self, other: &NFA) -> Self {
+        let mut nfa = Self::new();
+        let left = nfa.append_states(self);
+        let right = nfa.append_states(other);
+
+        nfa.start_state = self.start_state + left;
+        for end_state in self.end_states.iter() {
+            nfa.add_transition(end_state + left, RE::EPSILON, other.start_state + right);
+        }
+        nfa.end_states = other.end_states.iter().map(|state| state + right).collect();
+
+        nfa
+    }
+
+    /// returns a NFA for the kleene star of the language, the new start
+    /// state is final and it has no incoming transitions
+    pub fn star(&self) -> Self {
+        let mut nfa = Self::new();
+        nfa.start_state = nfa.add_state();
+        let offset = nfa.append_states(self);
+
+        nfa.add_transition(nfa.start_state, RE::EPSILON, self.start_state + offset);
+        for end_state in self.end_states.iter() {
+            nfa.add_transition(end_state + offset, RE::EPSILON, self.start_state + offset);
+        }
+        nfa.end_states = self.end_states.iter().map(|state| state + offset).collect();
+        nfa.end_states.push(nfa.start_state);
+
+        nfa
+    }
+
+    /// returns a NFA for the language concatenated one or more times
+    pub fn plus(&self) -> Self {
+        let mut nfa = Self::new();
+        let offset = nfa.append_states(self);
+
+        nfa.start_state = self.start_state + offset;
+        for end_state in self.end_states.iter() {
+            nfa.add_transition(end_state + offset, RE::EPSILON, nfa.start_state);
+        }
+        nfa.end_states = self.end_states.iter().map(|state| state + offset).collect();
+
+        nfa
+    }
+
+    /// returns a NFA for the reversed language: all the transitions are reversed,
+    /// the old start state is the final state, and a new start state moves
+    /// with ε to all the old final states
+    pub fn reverse(&self) -> Self {
+        let mut nfa = Self::new();
+        for _ in 0..self.num_states {
+            nfa.add_state();
+        }
+        nfa.used_alphabet = self.used_alphabet.clone();
+
+        for (from, adj) in self.transitions.iter().enumerate() {
+            for (ch, to_list) in adj.iter() {
+                for to in to_list {
+                    nfa.add_transition(*to, *ch, from);
+                }
+            }
+        }
+
+        nfa.start_state = nfa.add_state();
+        for end_state in self.end_states.iter() {
+            nfa.add_transition(nfa.start_state, RE::EPSILON, *end_state);
+        }
+        nfa.end_states = vec![self.start_state];
+
+        nfa
+    }
+
+    /// returns a NFA for the image of the language: every transition p -a-> q becomes
+    /// a path from p to q labelled h(a), or an ε transition if h(a) is empty.
+    /// The characters without an image are mapped to themselves, ε in an image is ignored.
+    pub fn homomorphism(&self, homomorphism: &Homomorphism) -> Self {
+        let mut nfa = Self::new();
+        for _ in 0..self.num_states {
+            nfa.add_state();
+        }
+        nfa.start_state = self.start_state;
+        nfa.end_states = self.end_states.clone();
+
+        for (from, adj) in self.transitions.iter().enumerate() {
+            for (ch, to_list) in adj.iter() {
+                let image: Vec<char> = match homomorphism.get(ch) {
+                    Some(image) => image.chars().filter(|c| *c != RE::EPSILON).collect(),
+                    None => vec![*ch],
+                };
+
+                for to in to_list {
+                    if image.is_empty() {
+                        nfa.add_transition(from, RE::EPSILON, *to);
+                        continue;
+                    }
+                    // intermediate states of the path, the last character reaches `to`
+                    let mut curr = from;
+                    for (i, image_ch) in image.iter().enumerate() {
+                        let next = if i + 1 == image.len() { *to } else { nfa.add_state() };
+                        nfa.add_transition(curr, *image_ch, next);
+                        curr = next;
+                    }
+                }
+            }
+        }
+
+        nfa
+    }
+
     pub fn get_start_state(&self) -> usize {
         self.start_state
     }
 
+    pub fn get_num_states(&self) -> usize {
+        self.num_states
+    }
+
+    pub fn get_end_states(&self) -> &Vec<usize> {
+        &self.end_states
+    }
+
+    pub fn get_transitions(&self) -> &Vec<BTreeMap<char, Vec<usize>>> {
+        &self.transitions
+    }
+
     pub fn get_alphabet(&self) -> Vec<char> {
         self.used_alphabet.iter().cloned().collect()
     }
@@ -82,6 +299,19 @@ impl NFA {
         closure
     }
 
+    /// returns the states that are final or have a transition on a character
+    /// that is not ε, the others can be ignored once the ε-closure is computed
+    pub fn get_important_states(&self, states: &BTreeSet<usize>) -> BTreeSet<usize> {
+        states
+            .iter()
+            .filter(|state| {
+                self.is_final_state(**state)
+                    || self.transitions[**state].keys().any(|ch| *ch != RE::EPSILON)
+            })
+            .cloned()
+            .collect()
+    }
+
     pub fn make_move(&self, states: &BTreeSet<usize>, c: char) -> BTreeSet<usize> {
         let mut new_states = BTreeSet::new();
 
@@ -96,6 +326,87 @@ impl NFA {
         new_states
     }
 
+    /// returns an equivalent NFA without ε transitions, with the same states.
+    ///
+    /// A state reads a character if any state in its ε-closure reads it, and it is
+    /// final if its ε-closure contains a final state.
+    pub fn remove_epsilon(&self) -> Self {
+        let mut nfa = Self::new();
+        nfa.num_states = self.num_states;
+        nfa.start_state = self.start_state;
+        nfa.used_alphabet = self.used_alphabet.clone();
+
+        for state in 0..self.num_states {
+            let closure = self.epsilon_closure(&vec![state]);
+            if self.contains_final_state(&closure) {
+                nfa.end_states.push(state);
+            }
+
+            let mut transitions = BTreeMap::new();
+            for ch in self.used_alphabet.iter() {
+                let next_states = self.make_move(&closure, *ch);
+                if !next_states.is_empty() {
+                    transitions.insert(*ch, next_states.into_iter().collect());
+                }
+            }
+            nfa.transitions.push(transitions);
+        }
+
+        nfa
+    }
+
+    /// returns an equivalent NFA with only the states that are reachable from the start
+    /// and from which a final state can be reached, the start state is always kept
+    pub fn remove_useless_states(&self) -> Self {
+        // the ε transitions are edges like the others
+        let successors: Vec<Vec<usize>> = self
+            .transitions
+            .iter()
+            .map(|adj| adj.values().flatten().cloned().collect())
+            .collect();
+        let reachable = reachable_from([self.start_state], &successors);
+        let live = co_reachable_from(self.end_states.iter().cloned(), &successors);
+
+        let mut useful: BTreeSet<usize> = reachable.intersection(&live).cloned().collect();
+        useful.insert(self.start_state);
+        self.restrict_to(&useful)
+    }
+
+    /// returns the NFA with only the given states, renumbered in increasing order
+    fn restrict_to(&self, states: &BTreeSet<usize>) -> Self {
+        let old_to_new: BTreeMap<usize, usize> = states
+            .iter()
+            .enumerate()
+            .map(|(new, old)| (*old, new))
+            .collect();
+
+        let mut nfa = Self::new();
+        nfa.num_states = states.len();
+        nfa.start_state = old_to_new[&self.start_state];
+        nfa.used_alphabet = self.used_alphabet.clone();
+        nfa.end_states = self
+            .end_states
+            .iter()
+            .filter_map(|state| old_to_new.get(state).cloned())
+            .collect();
+
+        for old in states.iter() {
+            let mut transitions = BTreeMap::new();
+            for (ch, to_list) in self.transitions[*old].iter() {
+                let to_list: Vec<usize> = to_list
+                    .iter()
+                    .filter_map(|to| old_to_new.get(to).cloned())
+                    .collect();
+                if !to_list.is_empty() {
+                    transitions.insert(*ch, to_list);
+                }
+            }
+            nfa.transitions.push(transitions);
+        }
+
+        nfa
+    }
+
     fn get_epsilon_transitions(&self, state: usize) -> Vec<usize> {
         let mut transitions = vec![];
         for i in self.transitions[state].keys() {
@@ -193,7 +504,10 @@ impl NFA {
                     .or_insert(Vec::new())
                     .push(end);
 
-                self.used_alphabet.insert(*c);
+                // an explicit ε in the expression is an epsilon transition
+                if *c != RE::EPSILON {
+                    self.used_alphabet.insert(*c);
+                }
 
                 (start, end)
             }
@@ -257,6 +571,9 @@ impl Into<Graph> for NFA {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::automata::test_utils::assert_same_language;
+    use crate::map;
+
     #[test]
     fn display_test() {
         let regex = RE::ReOperator::Or(
@@ -266,4 +583,82 @@ mod test {
         let nfa = NFA::from(&regex);
         println!("{:?}", nfa);
     }
+
+    #[test]
+    fn remove_epsilon() {
+        for str in ["a(b|c)*", "(ab|ε)*a", "a*b*", "(a|b)*abb"] {
+            let regex = RE::ReOperator::from_string(&str.to_string()).unwrap();
+            let nfa = NFA::from(&regex);
+            let epsilon_free = nfa.remove_epsilon();
+
+            assert!(epsilon_free
+                .transitions
+                .iter()
+                .all(|adj| !adj.contains_key(&RE::EPSILON)));
+            assert_same_language(&nfa, &epsilon_free);
+        }
+    }
+
+    fn nfa_from_regex(str: &str) -> NFA {
+        NFA::from(&RE::ReOperator::from_string(&str.to_string()).unwrap())
+    }
+
+    #[test]
+    fn regular_operations() {
+        let first = nfa_from_regex("ab*");
+        let second = nfa_from_regex("(c|a)b");
+
+        assert_same_language(&first.union(&second), &nfa_from_regex("ab*|(c|a)b"));
+        assert_same_language(&first.concat(&second), &nfa_from_regex("ab*(c|a)b"));
+        assert_same_language(&first.star(), &nfa_from_regex("(ab*)*"));
+        assert_same_language(&second.plus(), &nfa_from_regex("(c|a)b((c|a)b)*"));
+        assert_same_language(&first.reverse(), &nfa_from_regex("b*a"));
+    }
+
+    #[test]
+    fn composed_operations() {
+        let first = nfa_from_regex("a");
+        let second = nfa_from_regex("b");
+
+        let composed = first.union(&second).star().concat(&first.reverse().plus());
+        assert_same_language(&composed, &nfa_from_regex("(a|b)*aa*"));
+    }
+
+    #[test]
+    fn from_dfa() {
+        let regex = RE::ReOperator::from_string(&"(a|b)*abb".to_string()).unwrap();
+        let dfa = DFA::from(&regex).get_minimized_dfa();
+
+        assert_same_language(&NFA::from_dfa(&dfa), &NFA::from(&regex));
+    }
+
+    #[test]
+    fn from_state() {
+        // accepts words with an odd number of a, and ε moves from 0 to 2
+        let nfa = NFA::from_state(
+            3,
+            0,
+            vec![1],
+            vec![
+                map! { 'a' => vec![1], 'ε' => vec![2] },
+                map! { 'a' => vec![0] },
+                map! { 'b' => vec![0] },
+            ],
+            None,
+        );
+
+        assert_eq!(nfa.get_alphabet(), vec!['a', 'b']);
+        assert_same_language(&nfa, &nfa_from_regex("(b|aa)*a"));
+    }
+
+    #[test]
+    fn remove_useless_states() {
+        let regex = RE::ReOperator::from_string(&"(a|b)*c".to_string()).unwrap();
+        let nfa = NFA::from(&regex).remove_epsilon();
+        let trimmed = nfa.remove_useless_states();
+
+        // after removing the ε transitions most of thompson states become unreachable
+        assert!(trimmed.num_states < nfa.num_states);
+        assert_same_language(&nfa, &trimmed);
+    }
 }
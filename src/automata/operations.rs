@@ -0,0 +1,359 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::automata::regular_expression::EPSILON;
+use crate::automata::{DFA, NFA};
+
+/// string homomorphism, maps each character to a (possibly empty) word
+pub type Homomorphism = BTreeMap<char, String>;
+
+impl<T> DFA<T> {
+    /// returns the state reached reading the word from `state`, None if a transition is missing
+    fn run_from(&self, state: usize, word: &str) -> Option<usize> {
+        word.chars()
+            .try_fold(state, |curr, ch| self.get_next_state(curr, ch))
+    }
+
+    /// returns a DFA with the same transitions, with another start state and final states
+    fn with_start_and_end(&self, start_state: usize, end_states: Vec<usize>) -> Self {
+        DFA::from_state(
+            self.get_num_states(),
+            start_state,
+            end_states,
+            self.get_transitions().clone(),
+            Some(self.get_alphabet().clone()),
+        )
+    }
+
+    /// returns the pairs (state, other_state) reachable in the product of the two DFAs
+    /// starting from the given pair, moving with the same character in both
+    fn product_reachable<U>(&self, other: &DFA<U>, start: (usize, usize)) -> BTreeSet<(usize, usize)> {
+        let mut reached = BTreeSet::from([start]);
+        let mut queue = vec![start];
+
+        while let Some((state, other_state)) = queue.pop() {
+            for (ch, next) in self.get_transitions()[state].iter() {
+                if let Some(next_other) = other.get_next_state(other_state, *ch) {
+                    if reached.insert((*next, next_other)) {
+                        queue.push((*next, next_other));
+                    }
+                }
+            }
+        }
+
+        reached
+    }
+
+    /// left quotient by a word: w⁻¹L = { x | wx ∈ L }
+    pub fn left_quotient_by_word(&self, word: &str) -> Self {
+        match self.run_from(self.get_start_state(), word) {
+            Some(state) => self.with_start_and_end(state, self.get_end_states().clone()),
+            // the word is not a prefix of any word, the quotient is empty
+            None => self.with_start_and_end(self.get_start_state(), vec![]),
+        }
+    }
+
+    /// right quotient by a word: Lw⁻¹ = { x | xw ∈ L }
+    pub fn right_quotient_by_word(&self, word: &str) -> Self {
+        let end_states = (0..self.get_num_states())
+            .filter(|state| {
+                self.run_from(*state, word)
+                    .is_some_and(|end| self.is_final_state(end))
+            })
+            .collect();
+
+        self.with_start_and_end(self.get_start_state(), end_states)
+    }
+
+    /// right quotient by a language: L/K = { x | xy ∈ L for some y ∈ K }.
+    /// A state is final when, starting from it, a word of K leads to a final state.
+    pub fn right_quotient<U>(&self, other: &DFA<U>) -> Self {
+        let end_states = (0..self.get_num_states())
+            .filter(|state| {
+                self.product_reachable(other, (*state, other.get_start_state()))
+                    .iter()
+                    .any(|(end, other_end)| self.is_final_state(*end) && other.is_final_state(*other_end))
+            })
+            .collect();
+
+        self.with_start_and_end(self.get_start_state(), end_states)
+    }
+
+    /// left quotient by a language: K\L = { y | xy ∈ L for some x ∈ K }.
+    /// The result can start from every state reached reading a word of K.
+    pub fn left_quotient<U>(&self, other: &DFA<U>) -> NFA {
+        let start_states = self
+            .product_reachable(other, (self.get_start_state(), other.get_start_state()))
+            .into_iter()
+            .filter(|(_, other_state)| other.is_final_state(*other_state))
+            .map(|(state, _)| state)
+            .collect();
+
+        self.with_start_states(start_states, self.get_end_states().clone())
+    }
+
+    /// returns a NFA with the transitions of the DFA, a new start state
+    /// moves with ε to each one of the start states
+    fn with_start_states(&self, start_states: BTreeSet<usize>, end_states: Vec<usize>) -> NFA {
+        let mut transitions: Vec<BTreeMap<char, Vec<usize>>> = self
+            .get_transitions()
+            .iter()
+            .map(|adj| adj.iter().map(|(ch, to)| (*ch, vec![*to])).collect())
+            .collect();
+
+        let start_state = self.get_num_states();
+        transitions.push(BTreeMap::from([(EPSILON, start_states.into_iter().collect())]));
+
+        NFA::from_state(
+            self.get_num_states() + 1,
+            start_state,
+            end_states,
+            transitions,
+            Some(self.get_alphabet().clone()),
+        )
+    }
+
+    /// prefix closure: { x | xy ∈ L for some y }, every live state becomes final
+    pub fn prefix_closure(&self) -> Self {
+        self.with_start_and_end(self.get_start_state(), self.get_live_states().into_iter().collect())
+    }
+
+    /// suffix closure: { y | xy ∈ L for some x }, the result can start from every reachable state
+    pub fn suffix_closure(&self) -> NFA {
+        self.with_start_states(self.get_reachable_states(), self.get_end_states().clone())
+    }
+
+    /// factor closure: { y | xyz ∈ L for some x, z }, the result can start from
+    /// every reachable state and every live state is final
+    pub fn factor_closure(&self) -> NFA {
+        self.with_start_states(
+            self.get_reachable_states(),
+            self.get_live_states().into_iter().collect(),
+        )
+    }
+
+    /// shuffle of the two languages: all the interleavings of a word of each language.
+    /// The states are pairs of states, and each character moves only one of the two.
+    pub fn shuffle<U>(&self, other: &DFA<U>) -> NFA {
+        let start = (self.get_start_state(), other.get_start_state());
+        let mut pair_to_index = BTreeMap::from([(start, 0)]);
+        let mut pairs = vec![start];
+        let mut transitions: Vec<BTreeMap<char, Vec<usize>>> = vec![];
+
+        let mut curr = 0;
+        while curr < pairs.len() {
+            let (state, other_state) = pairs[curr];
+            let mut moves: Vec<(char, (usize, usize))> = vec![];
+            for (ch, next) in self.get_transitions()[state].iter() {
+                moves.push((*ch, (*next, other_state)));
+            }
+            for (ch, next) in other.get_transitions()[other_state].iter() {
+                moves.push((*ch, (state, *next)));
+            }
+
+            let mut adj: BTreeMap<char, Vec<usize>> = BTreeMap::new();
+            for (ch, next_pair) in moves {
+                let next_index = *pair_to_index.entry(next_pair).or_insert_with(|| {
+                    pairs.push(next_pair);
+                    pairs.len() - 1
+                });
+                let targets = adj.entry(ch).or_default();
+                if !targets.contains(&next_index) {
+                    targets.push(next_index);
+                }
+            }
+            transitions.push(adj);
+            curr += 1;
+        }
+
+        let end_states = pairs
+            .iter()
+            .enumerate()
+            .filter(|(_, (state, other_state))| {
+                self.is_final_state(*state) && other.is_final_state(*other_state)
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        NFA::from_state(pairs.len(), 0, end_states, transitions, None)
+    }
+
+    /// inverse homomorphism: h⁻¹(L) = { x | h(x) ∈ L }. The alphabet of the result is
+    /// the domain of the homomorphism together with the alphabet of the DFA, and reading
+    /// a moves like reading h(a). Like in `NFA::homomorphism` the characters without an
+    /// image are mapped to themselves, ε in an image is ignored.
+    pub fn inverse_homomorphism(&self, homomorphism: &Homomorphism) -> Self {
+        let mut images: BTreeMap<char, String> = homomorphism
+            .iter()
+            .map(|(ch, image)| (*ch, image.chars().filter(|c| *c != EPSILON).collect()))
+            .collect();
+        for ch in self.get_alphabet().iter() {
+            images.entry(*ch).or_insert_with(|| ch.to_string());
+        }
+
+        let transitions = (0..self.get_num_states())
+            .map(|state| {
+                images
+                    .iter()
+                    .filter_map(|(ch, image)| self.run_from(state, image).map(|next| (*ch, next)))
+                    .collect()
+            })
+            .collect();
+
+        DFA::from_state(
+            self.get_num_states(),
+            self.get_start_state(),
+            self.get_end_states().clone(),
+            transitions,
+            Some(images.keys().cloned().collect()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::automata::test_utils::{dfa_from_regex, words_up_to};
+    use crate::automata::NfaStates;
+
+    fn nfa_accepts(nfa: &NFA, word: &str) -> bool {
+        DFA::from(nfa).accepts(word)
+    }
+
+    #[test]
+    fn quotients_by_word() {
+        let dfa = dfa_from_regex("a(ab|b)*c|bca");
+        let words = words_up_to(&['a', 'b', 'c'], 5);
+
+        for quotient in ["", "a", "ab", "bc", "c"] {
+            let left = dfa.left_quotient_by_word(quotient);
+            let right = dfa.right_quotient_by_word(quotient);
+            for word in words.iter() {
+                assert_eq!(left.accepts(word), dfa.accepts(&format!("{}{}", quotient, word)));
+                assert_eq!(right.accepts(word), dfa.accepts(&format!("{}{}", word, quotient)));
+            }
+        }
+    }
+
+    #[test]
+    fn quotients_by_language() {
+        let dfa = dfa_from_regex("a(ab|b)*c|bca");
+        // a finite language, so the brute force check is exact
+        let other_words = ["b", "bc", "ca", "ε"];
+        let other = dfa_from_regex("b|bc|ca|ε");
+
+        let left = dfa.left_quotient(&other);
+        let right = dfa.right_quotient(&other);
+        for word in words_up_to(&['a', 'b', 'c'], 5) {
+            let other_words = other_words.iter().map(|other| other.replace('ε', ""));
+            let in_left = other_words
+                .clone()
+                .any(|other| dfa.accepts(&format!("{}{}", other, word)));
+            let in_right = other_words.clone().any(|other| dfa.accepts(&format!("{}{}", word, other)));
+
+            assert_eq!(nfa_accepts(&left, &word), in_left);
+            assert_eq!(right.accepts(&word), in_right);
+        }
+    }
+
+    #[test]
+    fn closures() {
+        let dfa = dfa_from_regex("ab*c|ba");
+        let words = words_up_to(&['a', 'b', 'c'], 4);
+        // every word of the language with at most 6 characters
+        let language: Vec<String> = words_up_to(&['a', 'b', 'c'], 6)
+            .into_iter()
+            .filter(|word| dfa.accepts(word))
+            .collect();
+
+        let prefix = dfa.prefix_closure();
+        let suffix = dfa.suffix_closure();
+        let factor = dfa.factor_closure();
+        for word in words.iter() {
+            assert_eq!(prefix.accepts(word), language.iter().any(|w| w.starts_with(word.as_str())));
+            assert_eq!(nfa_accepts(&suffix, word), language.iter().any(|w| w.ends_with(word.as_str())));
+            assert_eq!(nfa_accepts(&factor, word), language.iter().any(|w| w.contains(word.as_str())));
+        }
+    }
+
+    /// checks if the word is an interleaving of a word of first and a word of second,
+    /// trying all the ways to choose the characters that belong to the first word
+    fn is_shuffle(word: &str, first: &DFA<NfaStates>, second: &DFA<NfaStates>) -> bool {
+        let chars: Vec<char> = word.chars().collect();
+        (0..1_usize << chars.len()).any(|mask| {
+            let (mut left, mut right) = (String::new(), String::new());
+            for (i, ch) in chars.iter().enumerate() {
+                if mask & (1 << i) != 0 {
+                    left.push(*ch);
+                } else {
+                    right.push(*ch);
+                }
+            }
+            first.accepts(&left) && second.accepts(&right)
+        })
+    }
+
+    fn apply(homomorphism: &Homomorphism, word: &str) -> String {
+        word.chars()
+            .map(|ch| homomorphism.get(&ch).cloned().unwrap_or_else(|| ch.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn homomorphism_image() {
+        // non erasing, so the preimages of a word are not longer than the word
+        let dfa = dfa_from_regex("a(ab|c)*");
+        let homomorphism = Homomorphism::from([('a', "01".to_string()), ('b', "1".to_string())]);
+        let image = NFA::from_dfa(&dfa).homomorphism(&homomorphism);
+
+        let expected: BTreeSet<String> = words_up_to(&['a', 'b', 'c'], 6)
+            .iter()
+            .filter(|word| dfa.accepts(word))
+            .map(|word| apply(&homomorphism, word))
+            .collect();
+        for word in words_up_to(&['0', '1', 'c'], 6) {
+            assert_eq!(nfa_accepts(&image, &word), expected.contains(&word));
+        }
+
+        // erasing on a finite language
+        let dfa = dfa_from_regex("ab|ba|abc|ε");
+        let homomorphism = Homomorphism::from([('a', String::new()), ('c', "cc".to_string())]);
+        let image = NFA::from_dfa(&dfa).homomorphism(&homomorphism);
+        for word in words_up_to(&['a', 'b', 'c'], 4) {
+            assert_eq!(nfa_accepts(&image, &word), ["b", "bcc", ""].contains(&word.as_str()));
+        }
+    }
+
+    #[test]
+    fn inverse_homomorphism() {
+        let dfa = dfa_from_regex("(ab|b)*c");
+        let homomorphism = Homomorphism::from([
+            ('x', "ab".to_string()),
+            ('y', "b".to_string()),
+            ('z', String::new()),
+            ('w', "bc".to_string()),
+        ]);
+        let inverse = dfa.inverse_homomorphism(&homomorphism);
+
+        for word in words_up_to(&['x', 'y', 'z', 'w'], 5) {
+            assert_eq!(inverse.accepts(&word), dfa.accepts(&apply(&homomorphism, &word)));
+        }
+
+        // c has no image, so it's mapped to itself like in the forward homomorphism
+        let homomorphism = Homomorphism::from([('a', "b".to_string()), ('b', "ab".to_string())]);
+        let inverse = dfa.inverse_homomorphism(&homomorphism);
+        for word in words_up_to(&['a', 'b', 'c'], 5) {
+            assert_eq!(inverse.accepts(&word), dfa.accepts(&apply(&homomorphism, &word)));
+        }
+    }
+
+    #[test]
+    fn shuffle() {
+        let first = dfa_from_regex("ab*");
+        let second = dfa_from_regex("cb|ε");
+        let shuffle = first.shuffle(&second);
+
+        for word in words_up_to(&['a', 'b', 'c'], 5) {
+            assert_eq!(nfa_accepts(&shuffle, &word), is_shuffle(&word, &first, &second));
+        }
+    }
+}
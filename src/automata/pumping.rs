@@ -0,0 +1,129 @@
+use crate::automata::DFA;
+
+/// Decomposition w = xyz of an accepted word given by the pumping lemma:
+/// reading y the run goes back to the state it was in after x,
+/// so x y^i z is accepted for every i.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PumpingDecomposition {
+    pub x: String,
+    pub y: String,
+    pub z: String,
+    /// states of the run while reading y, the first one is repeated at the end of y
+    pub loop_states: Vec<usize>,
+}
+
+impl PumpingDecomposition {
+    /// returns the word x y^times z
+    pub fn pump(&self, times: usize) -> String {
+        format!("{}{}{}", self.x, self.y.repeat(times), self.z)
+    }
+
+    /// returns the transitions (from, to) of the loop read by y
+    pub fn get_loop_edges(&self) -> Vec<(usize, usize)> {
+        self.loop_states
+            .iter()
+            .zip(self.loop_states.iter().cycle().skip(1))
+            .map(|(from, to)| (*from, *to))
+            .collect()
+    }
+}
+
+impl<T> DFA<T> {
+    /// the pumping length of the language, when called on the minimized DFA: the number
+    /// of states of the trimmed DFA, so the dead state is not counted. The run of an accepted
+    /// word only visits these states, so if the word is at least this long a state is repeated
+    pub fn get_pumping_length(&self) -> usize {
+        // the states kept by trim, the start state is always among them
+        let mut useful = self.get_useful_states();
+        useful.insert(self.get_start_state());
+        useful.len()
+    }
+
+    /// finds the decomposition xyz of the word looking for the first repeated state of the run,
+    /// so |xy| <= number of states and y is not empty.
+    ///
+    /// returns None if the word is not accepted or the run has no repeated state
+    pub fn get_pumping_decomposition(&self, word: &str) -> Option<PumpingDecomposition> {
+        if !self.accepts(word) {
+            return None;
+        }
+
+        let chars: Vec<char> = word.chars().collect();
+        let mut run = vec![self.get_start_state()];
+        for ch in chars.iter() {
+            let next = self.get_next_state(*run.last().unwrap(), *ch)?;
+            if let Some(loop_start) = run.iter().position(|state| *state == next) {
+                let loop_end = run.len();
+                return Some(PumpingDecomposition {
+                    x: chars[..loop_start].iter().collect(),
+                    y: chars[loop_start..loop_end].iter().collect(),
+                    z: chars[loop_end..].iter().collect(),
+                    loop_states: run[loop_start..].to_vec(),
+                });
+            }
+            run.push(next);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::automata::test_utils::dfa_from_regex;
+
+    #[test]
+    fn decomposition() {
+        let dfa = dfa_from_regex("a(bc)*d");
+        let decomposition = dfa.get_pumping_decomposition("abcbcd").unwrap();
+
+        assert_eq!(decomposition.x, "a");
+        assert_eq!(decomposition.y, "bc");
+        assert_eq!(decomposition.z, "bcd");
+        assert_eq!(decomposition.loop_states.len(), 2);
+        assert_eq!(decomposition.get_loop_edges().len(), 2);
+        assert_eq!(decomposition.pump(0), "abcd");
+        assert_eq!(decomposition.pump(2), "abcbcbcd");
+    }
+
+    #[test]
+    fn pumping_length_without_dead_state() {
+        let dfa = dfa_from_regex("a(bc)*d");
+
+        // the minimized DFA is complete, one of its states is dead
+        assert_eq!(dfa.get_num_states(), 5);
+        assert_eq!(dfa.get_pumping_length(), 4);
+        assert_eq!(dfa.get_pumping_length(), dfa.clone().trim().get_num_states());
+        assert_eq!(dfa_from_regex("ε").get_pumping_length(), 1);
+    }
+
+    #[test]
+    fn pumped_words_are_accepted() {
+        let dfa = dfa_from_regex("(ab|b)*c(a|cc)*");
+        let pumping_length = dfa.get_pumping_length();
+
+        for word in dfa.shortlex_words().take(200) {
+            let decomposition = dfa.get_pumping_decomposition(&word);
+            if word.chars().count() < pumping_length {
+                continue;
+            }
+
+            // every long enough word can be pumped
+            let decomposition = decomposition.unwrap();
+            assert!(!decomposition.y.is_empty());
+            assert!(decomposition.x.chars().count() + decomposition.y.chars().count() <= pumping_length);
+            for times in 0..4 {
+                assert!(dfa.accepts(&decomposition.pump(times)));
+            }
+        }
+    }
+
+    #[test]
+    fn no_decomposition() {
+        let dfa = dfa_from_regex("ab|abc");
+
+        assert_eq!(dfa.get_pumping_decomposition("abc"), None);
+        // not accepted
+        assert_eq!(dfa.get_pumping_decomposition("ba"), None);
+    }
+}
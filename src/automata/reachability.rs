@@ -0,0 +1,35 @@
+use std::collections::BTreeSet;
+
+/// returns the states that can be reached from the sources following the edges,
+/// `adj[state]` are the successors of the state. The sources are included
+pub fn reachable_from(
+    sources: impl IntoIterator<Item = usize>,
+    adj: &[Vec<usize>],
+) -> BTreeSet<usize> {
+    let mut reachable: BTreeSet<usize> = sources.into_iter().collect();
+    let mut queue: Vec<usize> = reachable.iter().cloned().collect();
+    while let Some(state) = queue.pop() {
+        for next in adj[state].iter() {
+            if reachable.insert(*next) {
+                queue.push(*next);
+            }
+        }
+    }
+
+    reachable
+}
+
+/// returns the states from which one of the targets can be reached
+pub fn co_reachable_from(
+    targets: impl IntoIterator<Item = usize>,
+    adj: &[Vec<usize>],
+) -> BTreeSet<usize> {
+    let mut reverse_adj = vec![vec![]; adj.len()];
+    for (from, successors) in adj.iter().enumerate() {
+        for to in successors.iter() {
+            reverse_adj[*to].push(from);
+        }
+    }
+
+    reachable_from(targets, &reverse_adj)
+}
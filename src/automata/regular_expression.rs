@@ -1,24 +1,36 @@
 use std::error::Error;
+use std::fmt;
 use std::iter::Peekable;
 use std::str::Chars;
 
 use crate::error::{InvalidCharacter, InvalidTokenError, UnvalidParentesis};
 use crate::utils::{Graph, IndNode};
 
+pub const EPSILON: char = 'ε';
+
+/// shows the word, the empty word as ε
+pub fn word_to_string(word: &str) -> String {
+    if word.is_empty() {
+        EPSILON.to_string()
+    } else {
+        word.to_string()
+    }
+}
+
 /// Structure that represents a regular expression parse tree
 /// The current regular expression is defined by the following grammar:
 /// 
 /// ```text
 /// S -> A | S(* | S)
 /// 
-/// A -> [a-z] | [A-Z] | [0-9]
+/// A -> [a-z] | [A-Z] | [0-9] | ε
 /// ```
 /// 
 /// **NOTE**: 'ε' is considered as a special symbol
-/// it is not supported in this grammar, but is can be used as an 
-/// external symbol.
+/// it can only be written explicitly, empty alternatives are not
+/// supported.
 /// 
-/// If we wanted to include 'ε' in the grammar, we would have 
+/// If we wanted to accept empty alternatives, we would have 
 /// to accept strings like `|||` which is non-sensical.
 #[derive(Debug, Clone)]
 pub enum ReOperator {
@@ -59,6 +71,102 @@ impl ReOperator {
         Ok(*res)
     }
     
+    /// returns true if the empty word is in the language of the expression
+    pub fn is_nullable(&self) -> bool {
+        match self {
+            ReOperator::Char(c) => *c == EPSILON,
+            ReOperator::Concat(left, right) => left.is_nullable() && right.is_nullable(),
+            ReOperator::Or(left, right) => left.is_nullable() || right.is_nullable(),
+            ReOperator::KleeneStar(_) => true,
+        }
+    }
+
+    /// returns an equivalent expression, with some trivial redundancies removed:
+    /// - concatenations with ε
+    /// - duplicated alternatives and ε alternatives when another one is nullable
+    /// - stars of ε, of stars and of alternatives containing ε
+    pub fn simplify(&self) -> ReOperator {
+        match self {
+            ReOperator::Char(c) => ReOperator::Char(*c),
+            ReOperator::Concat(left, right) => {
+                let left = left.simplify();
+                let right = right.simplify();
+
+                if left == ReOperator::Char(EPSILON) {
+                    right
+                } else if right == ReOperator::Char(EPSILON) {
+                    left
+                } else {
+                    ReOperator::Concat(Box::new(left), Box::new(right))
+                }
+            }
+            ReOperator::Or(_, _) => {
+                let mut alternatives: Vec<ReOperator> = vec![];
+                for alternative in self.alternatives() {
+                    let alternative = alternative.simplify();
+                    if !alternatives.contains(&alternative) {
+                        alternatives.push(alternative);
+                    }
+                }
+
+                let epsilon = ReOperator::Char(EPSILON);
+                if alternatives.iter().any(|alt| *alt != epsilon && alt.is_nullable()) {
+                    alternatives.retain(|alt| *alt != epsilon);
+                }
+
+                Self::from_alternatives(alternatives)
+            }
+            ReOperator::KleeneStar(inner) => {
+                let epsilon = ReOperator::Char(EPSILON);
+                let inner = match inner.simplify() {
+                    ReOperator::KleeneStar(inner) => *inner,
+                    inner @ ReOperator::Or(_, _) => {
+                        let alternatives = inner
+                            .alternatives()
+                            .into_iter()
+                            .filter(|alt| **alt != epsilon)
+                            .cloned()
+                            .collect::<Vec<_>>();
+                        if alternatives.is_empty() {
+                            epsilon.clone()
+                        } else {
+                            Self::from_alternatives(alternatives)
+                        }
+                    }
+                    inner => inner,
+                };
+
+                if inner == epsilon {
+                    epsilon
+                } else {
+                    ReOperator::KleeneStar(Box::new(inner))
+                }
+            }
+        }
+    }
+
+    /// returns the operands of a chain of Or operators
+    fn alternatives(&self) -> Vec<&ReOperator> {
+        match self {
+            ReOperator::Or(left, right) => {
+                let mut alternatives = left.alternatives();
+                alternatives.append(&mut right.alternatives());
+                alternatives
+            }
+            _ => vec![self],
+        }
+    }
+
+    /// builds a right associative chain of Or operators, alternatives must not be empty
+    fn from_alternatives(mut alternatives: Vec<ReOperator>) -> ReOperator {
+        let mut result = alternatives.pop().unwrap();
+        while let Some(alternative) = alternatives.pop() {
+            result = ReOperator::Or(Box::new(alternative), Box::new(result));
+        }
+
+        result
+    }
+
     /// returns a character rapresentation of the Reoperator
     fn label(&self) -> String {
         match self {
@@ -254,7 +362,7 @@ impl ReOperator {
         }
 
         match curr_char {
-            'a'..='z' | 'A'..='Z' | '0'..='9' => {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | EPSILON => {
                 if chars.peek() == Some(&'*') {
                     chars.next();
                     return Ok(Box::new(ReOperator::KleeneStar(Box::new(ReOperator::Char(curr_char)))));
@@ -269,7 +377,7 @@ impl ReOperator {
             }
             _ => {
                 return Err(Box::new(InvalidTokenError::new(
-                    "Invalid character, only ([a-z]|[A-Z]|[0-9]|\\*)* is accepted".to_string(),
+                    "Invalid character, only ([a-z]|[A-Z]|[0-9]|ε|\\*)* is accepted".to_string(),
                 )));
             }
         }
@@ -279,7 +387,31 @@ impl ReOperator {
     /// a character is valid when it satysfies the A non terminal described in [ReOperator]
     /// and special characters like (, ), |, *
     fn is_valid_char(c: char) -> bool {
-        c.is_ascii_alphabetic() || c.is_ascii_digit() || c == '|' || c == '*' || c == '(' || c == ')'
+        c.is_ascii_alphabetic() || c.is_ascii_digit() || c == '|' || c == '*' || c == '(' || c == ')' || c == EPSILON
+    }
+}
+
+/// prints the expression in the same syntax accepted by [ReOperator::from_string]
+impl fmt::Display for ReOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReOperator::Char(c) => write!(f, "{}", c),
+            ReOperator::Concat(left, right) => {
+                for operand in [left, right] {
+                    if let ReOperator::Or(_, _) = **operand {
+                        write!(f, "({})", operand)?;
+                    } else {
+                        write!(f, "{}", operand)?;
+                    }
+                }
+                Ok(())
+            }
+            ReOperator::Or(left, right) => write!(f, "{}|{}", left, right),
+            ReOperator::KleeneStar(inner) => match **inner {
+                ReOperator::Char(c) => write!(f, "{}*", c),
+                _ => write!(f, "({})*", inner),
+            },
+        }
     }
 }
 
@@ -437,5 +569,65 @@ mod test {
             let tree = ReOperator::from_string(&str);
             assert!(!tree.is_err());
         }
+
+        #[test]
+        fn explicit_epsilon() {
+            let str = "a|ε".to_string();
+            let tree = ReOperator::from_string(&str).unwrap();
+
+            assert_eq!(
+                tree,
+                ReOperator::Or(
+                    Box::new(ReOperator::Char('a')),
+                    Box::new(ReOperator::Char(EPSILON)),
+                )
+            );
+        }
+    }
+
+    mod print_and_simplify {
+        use super::*;
+
+        fn parse(str: &str) -> ReOperator {
+            ReOperator::from_string(&str.to_string()).unwrap()
+        }
+
+        #[test]
+        fn print_round_trip() {
+            for str in ["a(b|c)", "(a|b)*c", "ab*|c", "a(bc)*", "(a|ε)b", "((a|b)c)*"] {
+                let tree = parse(str);
+                assert_eq!(parse(&tree.to_string()), tree);
+            }
+        }
+
+        #[test]
+        fn print_adds_parens() {
+            let tree = ReOperator::Concat(
+                Box::new(ReOperator::Or(
+                    Box::new(ReOperator::Char('a')),
+                    Box::new(ReOperator::Char('b')),
+                )),
+                Box::new(ReOperator::KleeneStar(Box::new(ReOperator::Concat(
+                    Box::new(ReOperator::Char('c')),
+                    Box::new(ReOperator::Char('d')),
+                )))),
+            );
+
+            assert_eq!(tree.to_string(), "(a|b)(cd)*");
+        }
+
+        #[test]
+        fn simplify_epsilon() {
+            assert_eq!(parse("εaε").simplify(), parse("a"));
+            assert_eq!(parse("(ε)*").simplify(), parse("ε"));
+            assert_eq!(parse("ε|a*").simplify(), parse("a*"));
+            assert_eq!(parse("(ε|a)*").simplify(), parse("a*"));
+        }
+
+        #[test]
+        fn simplify_alternatives_and_stars() {
+            assert_eq!(parse("a|b|a").simplify(), parse("a|b"));
+            assert_eq!(parse("((a)*)*").simplify(), parse("a*"));
+        }
     }
 }
@@ -0,0 +1,287 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::automata::regular_expression::{ReOperator, EPSILON};
+use crate::automata::{DFA, NFA};
+use crate::utils::Graph;
+
+/// heuristic used to choose the next state to eliminate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EliminationOrder {
+    /// eliminate the states in increasing index order
+    Ascending,
+    /// eliminate first the state with the fewest incoming * outgoing edges,
+    /// so the fewest new edges are created
+    FewestEdges,
+    /// eliminate first the state with the shortest labels on its edges
+    ShortestLabels,
+}
+
+impl EliminationOrder {
+    pub const ALL: [EliminationOrder; 3] = [
+        EliminationOrder::Ascending,
+        EliminationOrder::FewestEdges,
+        EliminationOrder::ShortestLabels,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EliminationOrder::Ascending => "ascending index",
+            EliminationOrder::FewestEdges => "fewest edges",
+            EliminationOrder::ShortestLabels => "shortest labels",
+        }
+    }
+}
+
+/// Generalized NFA, the edges are labelled with regular expressions.
+/// The start state has no incoming edges and the single final state has
+/// no outgoing edges, a missing edge is the empty language.
+///
+/// States keep the index of the automaton they come from, the start and
+/// the end state are added after them.
+#[derive(Debug, Clone)]
+pub struct Gnfa {
+    start_state: usize,
+    end_state: usize,
+    // states that have not been eliminated yet, start and end excluded
+    states: BTreeSet<usize>,
+    edges: BTreeMap<(usize, usize), ReOperator>,
+}
+
+impl Gnfa {
+    fn new(num_states: usize) -> Self {
+        Self {
+            start_state: num_states,
+            end_state: num_states + 1,
+            states: BTreeSet::new(),
+            edges: BTreeMap::new(),
+        }
+    }
+
+    /// adds an edge, if there's already one between the two states the labels are joined with an or
+    fn add_edge(&mut self, from: usize, to: usize, regex: ReOperator) {
+        let regex = match self.edges.remove(&(from, to)) {
+            Some(old) => ReOperator::Or(Box::new(old), Box::new(regex)).simplify(),
+            None => regex,
+        };
+        self.edges.insert((from, to), regex);
+    }
+
+    /// removes the state, and replaces every path p -> state -> r with an
+    /// edge p -> r labelled (p, state)(state, state)*(state, r)
+    pub fn eliminate(&mut self, state: usize) {
+        let self_loop = self.edges.remove(&(state, state));
+        let incoming: Vec<(usize, ReOperator)> = self
+            .edges
+            .iter()
+            .filter(|((_, to), _)| *to == state)
+            .map(|((from, _), regex)| (*from, regex.clone()))
+            .collect();
+        let outgoing: Vec<(usize, ReOperator)> = self
+            .edges
+            .iter()
+            .filter(|((from, _), _)| *from == state)
+            .map(|((_, to), regex)| (*to, regex.clone()))
+            .collect();
+        self.edges.retain(|(from, to), _| *from != state && *to != state);
+
+        for (from, in_regex) in incoming.iter() {
+            for (to, out_regex) in outgoing.iter() {
+                let mut regex = in_regex.clone();
+                if let Some(loop_regex) = &self_loop {
+                    let star = ReOperator::KleeneStar(Box::new(loop_regex.clone()));
+                    regex = ReOperator::Concat(Box::new(regex), Box::new(star));
+                }
+                regex = ReOperator::Concat(Box::new(regex), Box::new(out_regex.clone()));
+
+                self.add_edge(*from, *to, regex.simplify());
+            }
+        }
+
+        self.states.remove(&state);
+    }
+
+    /// returns the next state to eliminate according to the heuristic,
+    /// None if only the start and end state are left
+    pub fn next_state(&self, order: EliminationOrder) -> Option<usize> {
+        let adjacent = |state: usize| {
+            self.edges
+                .iter()
+                .filter(move |((from, to), _)| *from != *to && (*from == state || *to == state))
+        };
+
+        match order {
+            EliminationOrder::Ascending => self.states.iter().next().cloned(),
+            EliminationOrder::FewestEdges => self.states.iter().cloned().min_by_key(|state| {
+                let incoming = adjacent(*state).filter(|((_, to), _)| to == state).count();
+                let outgoing = adjacent(*state).filter(|((from, _), _)| from == state).count();
+                incoming * outgoing
+            }),
+            EliminationOrder::ShortestLabels => self.states.iter().cloned().min_by_key(|state| {
+                adjacent(*state)
+                    .map(|(_, regex)| regex.to_string().len())
+                    .sum::<usize>()
+            }),
+        }
+    }
+
+    /// returns the regex of the language, None if the language is empty
+    pub fn into_regex(self, order: EliminationOrder) -> Option<ReOperator> {
+        self.into_regex_with_steps(order).0
+    }
+
+    /// eliminates all the states, and returns the regex of the language (None if it's empty)
+    /// together with the graph of the GNFA after each step
+    pub fn into_regex_with_steps(mut self, order: EliminationOrder) -> (Option<ReOperator>, Vec<(String, Graph)>) {
+        let mut steps = vec![("initial GNFA".to_string(), Graph::from(&self))];
+
+        while let Some(state) = self.next_state(order) {
+            self.eliminate(state);
+            steps.push((format!("eliminated state {}", state), Graph::from(&self)));
+        }
+
+        let regex = self
+            .edges
+            .get(&(self.start_state, self.end_state))
+            .map(|regex| regex.simplify());
+
+        (regex, steps)
+    }
+}
+
+impl<T> From<&DFA<T>> for Gnfa {
+    /// only the useful states of the DFA are kept, the others can't
+    /// contribute to the language
+    fn from(dfa: &DFA<T>) -> Self {
+        let mut gnfa = Self::new(dfa.get_num_states());
+        let useful = dfa.get_useful_states();
+        gnfa.states = useful.clone();
+
+        for from in useful.iter() {
+            for (ch, to) in dfa.get_transitions()[*from].iter() {
+                if useful.contains(to) {
+                    gnfa.add_edge(*from, *to, ReOperator::Char(*ch));
+                }
+            }
+        }
+
+        if useful.contains(&dfa.get_start_state()) {
+            gnfa.add_edge(gnfa.start_state, dfa.get_start_state(), ReOperator::Char(EPSILON));
+        }
+        for end_state in dfa.get_end_states() {
+            if useful.contains(end_state) {
+                gnfa.add_edge(*end_state, gnfa.end_state, ReOperator::Char(EPSILON));
+            }
+        }
+
+        gnfa
+    }
+}
+
+impl From<&NFA> for Gnfa {
+    fn from(nfa: &NFA) -> Self {
+        let mut gnfa = Self::new(nfa.get_num_states());
+        gnfa.states = (0..nfa.get_num_states()).collect();
+
+        for (from, adj) in nfa.get_transitions().iter().enumerate() {
+            for (ch, to_list) in adj.iter() {
+                for to in to_list {
+                    gnfa.add_edge(from, *to, ReOperator::Char(*ch));
+                }
+            }
+        }
+
+        gnfa.add_edge(gnfa.start_state, nfa.get_start_state(), ReOperator::Char(EPSILON));
+        for end_state in nfa.get_end_states() {
+            gnfa.add_edge(*end_state, gnfa.end_state, ReOperator::Char(EPSILON));
+        }
+
+        gnfa
+    }
+}
+
+impl From<&Gnfa> for Graph {
+    fn from(gnfa: &Gnfa) -> Graph {
+        let mut graph = Graph::new();
+
+        let start_node = graph.add_node(Some("s".to_string()));
+        let mut translate_table = BTreeMap::new();
+        translate_table.insert(gnfa.start_state, start_node);
+        for state in gnfa.states.iter() {
+            translate_table.insert(*state, graph.add_node(Some(format!("{}", state))));
+        }
+        translate_table.insert(gnfa.end_state, graph.add_node(Some("e".to_string())));
+
+        for ((from, to), regex) in gnfa.edges.iter() {
+            graph.add_edge(
+                translate_table[from],
+                translate_table[to],
+                Some(regex.to_string()),
+            );
+        }
+        graph.start_node = Some(start_node);
+
+        graph
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::automata::test_utils::assert_same_language;
+
+    fn parse(str: &str) -> ReOperator {
+        ReOperator::from_string(&str.to_string()).unwrap()
+    }
+
+    #[test]
+    fn dfa_round_trip() {
+        for str in ["a(b|c)*", "(ab|ba)*a", "a*b*", "(a|b)*abb", "ε|ab"] {
+            let regex = parse(str);
+            let dfa = DFA::from(&regex).get_minimized_dfa();
+
+            for order in EliminationOrder::ALL {
+                let result = Gnfa::from(&dfa).into_regex(order).unwrap();
+                assert_same_language(&regex, &result);
+
+                // the printed result can be parsed back
+                assert_same_language(&parse(&result.to_string()), &result);
+            }
+        }
+    }
+
+    #[test]
+    fn nfa_round_trip() {
+        let regex = parse("(a|bc)*d");
+        let nfa = NFA::from(&regex);
+
+        for order in EliminationOrder::ALL {
+            let result = Gnfa::from(&nfa).into_regex(order).unwrap();
+            assert_same_language(&regex, &result);
+        }
+    }
+
+    #[test]
+    fn single_char() {
+        let dfa = DFA::from(&parse("a")).get_minimized_dfa();
+        let result = Gnfa::from(&dfa).into_regex(EliminationOrder::Ascending);
+
+        assert_eq!(result, Some(parse("a")));
+    }
+
+    #[test]
+    fn empty_language() {
+        let dfa: DFA<usize> = DFA::from_state(1, 0, vec![], vec![BTreeMap::new()], None);
+
+        assert_eq!(Gnfa::from(&dfa).into_regex(EliminationOrder::FewestEdges), None);
+    }
+
+    #[test]
+    fn steps_count() {
+        let dfa = DFA::from(&parse("ab*")).get_minimized_dfa();
+        let useful = dfa.get_useful_states().len();
+        let (_, steps) = Gnfa::from(&dfa).into_regex_with_steps(EliminationOrder::Ascending);
+
+        // the initial GNFA, and one step for each eliminated state
+        assert_eq!(steps.len(), useful + 1);
+    }
+}
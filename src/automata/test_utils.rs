@@ -0,0 +1,33 @@
+use crate::automata::{NfaStates, ReOperator, DFA};
+
+pub fn dfa_from_regex(regex: &str) -> DFA<NfaStates> {
+    let regex = ReOperator::from_string(&regex.to_string()).unwrap();
+    DFA::from(&regex).get_minimized_dfa()
+}
+
+/// checks the inclusion in both directions, the automata or regular
+/// expressions are compared through their DFAs
+pub fn assert_same_language<T>(first: &T, second: &T)
+where
+    for<'a> DFA<NfaStates>: From<&'a T>,
+{
+    let first = DFA::from(first);
+    let second = DFA::from(second);
+
+    assert_eq!(first.get_subset_witness(&second), None);
+    assert_eq!(second.get_subset_witness(&first), None);
+}
+
+/// all the words over the alphabet with length up to max_len
+pub fn words_up_to(alphabet: &[char], max_len: usize) -> Vec<String> {
+    let mut words = vec![String::new()];
+    let mut last = vec![String::new()];
+    for _ in 0..max_len {
+        last = last
+            .iter()
+            .flat_map(|word| alphabet.iter().map(move |ch| format!("{}{}", word, ch)))
+            .collect();
+        words.extend(last.iter().cloned());
+    }
+    words
+}
@@ -0,0 +1,231 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use crate::automata::DFA;
+
+/// Iterator over the words accepted by a DFA in shortlex order,
+/// shorter words first and words of the same length in lexicographic order.
+///
+/// The words are explored in bfs order, and only through live states,
+/// so every word in the queue is the prefix of some accepted word.
+pub struct ShortlexWords<'a, T> {
+    dfa: &'a DFA<T>,
+    alphabet: Vec<char>,
+    live: BTreeSet<usize>,
+    queue: VecDeque<(String, usize)>,
+}
+
+impl<T> Iterator for ShortlexWords<'_, T> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((word, state)) = self.queue.pop_front() {
+            for ch in self.alphabet.iter() {
+                if let Some(next) = self.dfa.get_next_state(state, *ch) {
+                    if self.live.contains(&next) {
+                        let mut next_word = word.clone();
+                        next_word.push(*ch);
+                        self.queue.push_back((next_word, next));
+                    }
+                }
+            }
+
+            if self.dfa.is_final_state(state) {
+                return Some(word);
+            }
+        }
+
+        None
+    }
+}
+
+impl<T> DFA<T> {
+    pub fn shortlex_words(&self) -> ShortlexWords<'_, T> {
+        let live = self.get_live_states();
+        let mut alphabet = self.get_alphabet().clone();
+        alphabet.sort();
+
+        let mut queue = VecDeque::new();
+        if live.contains(&self.get_start_state()) {
+            queue.push_back((String::new(), self.get_start_state()));
+        }
+
+        ShortlexWords {
+            dfa: self,
+            alphabet,
+            live,
+            queue,
+        }
+    }
+
+    /// returns a vector where the i-th element is the number of accepted words of length i,
+    /// for every length up to max_len (included).
+    ///
+    /// ways[state] is the number of words of the current length that lead from the
+    /// start state to state.
+    pub fn count_words_by_length(&self, max_len: usize) -> Vec<usize> {
+        let mut ways = vec![0_usize; self.get_num_states()];
+        ways[self.get_start_state()] = 1;
+
+        let mut counts = Vec::with_capacity(max_len + 1);
+        for len in 0..=max_len {
+            let accepted = self
+                .get_end_states()
+                .iter()
+                .fold(0_usize, |acc, state| acc.saturating_add(ways[*state]));
+            counts.push(accepted);
+
+            if len == max_len {
+                break;
+            }
+
+            let mut next_ways = vec![0_usize; self.get_num_states()];
+            for (from, adj) in self.get_transitions().iter().enumerate() {
+                for to in adj.values() {
+                    next_ways[*to] = next_ways[*to].saturating_add(ways[from]);
+                }
+            }
+            ways = next_ways;
+        }
+
+        counts
+    }
+
+    /// returns the exponential growth rate of the language: the number of accepted
+    /// words of length n grows like growth_rate^n (up to a polynomial factor).
+    /// Its inverse is the radius of convergence of the generating function.
+    ///
+    /// The rate is the spectral radius of the adjacency matrix of the useful states,
+    /// which is the maximum spectral radius of its strongly connected components.
+    /// Finite languages have rate 0, languages with polynomial growth have rate 1.
+    pub fn growth_rate(&self) -> f64 {
+        let live = self.get_live_states();
+        let useful: Vec<usize> = self
+            .get_reachable_states()
+            .into_iter()
+            .filter(|state| live.contains(state))
+            .collect();
+
+        let reach: Vec<BTreeSet<usize>> = useful
+            .iter()
+            .map(|state| self.get_reachable_from(*state, &useful))
+            .collect();
+
+        let mut assigned = BTreeSet::new();
+        let mut rate: f64 = 0.;
+        for (i, state) in useful.iter().enumerate() {
+            if assigned.contains(state) {
+                continue;
+            }
+
+            // the component of state contains the states that reach it and are reached by it
+            let component: Vec<usize> = useful
+                .iter()
+                .enumerate()
+                .filter(|(j, other)| reach[i].contains(other) && reach[*j].contains(state))
+                .map(|(_, other)| *other)
+                .collect();
+            assigned.extend(component.iter().cloned());
+
+            rate = rate.max(self.component_spectral_radius(&component));
+        }
+
+        rate
+    }
+
+    /// states reachable from `state` (with at least one transition) moving only through `allowed`
+    fn get_reachable_from(&self, state: usize, allowed: &[usize]) -> BTreeSet<usize> {
+        let mut reached = BTreeSet::new();
+        let mut queue = vec![state];
+
+        while let Some(curr) = queue.pop() {
+            for next in self.get_transitions()[curr].values() {
+                if allowed.contains(next) && reached.insert(*next) {
+                    queue.push(*next);
+                }
+            }
+        }
+
+        reached
+    }
+
+    /// power iteration on M + I, where M is the adjacency matrix of the component.
+    /// Adding the identity makes the matrix aperiodic so the iteration converges,
+    /// and shifts the spectral radius by exactly one.
+    fn component_spectral_radius(&self, component: &[usize]) -> f64 {
+        const MAX_ITERATIONS: usize = 10000;
+        const PRECISION: f64 = 1e-12;
+
+        let size = component.len();
+        let mut matrix = vec![vec![0.; size]; size];
+        for (i, from) in component.iter().enumerate() {
+            matrix[i][i] += 1.;
+            for to in self.get_transitions()[*from].values() {
+                if let Some(j) = component.iter().position(|state| state == to) {
+                    matrix[i][j] += 1.;
+                }
+            }
+        }
+
+        let mut vector = vec![1.; size];
+        let mut radius: f64 = 0.;
+        for _ in 0..MAX_ITERATIONS {
+            let next: Vec<f64> = matrix
+                .iter()
+                .map(|row| row.iter().zip(vector.iter()).map(|(a, b)| a * b).sum())
+                .collect();
+            let norm = next.iter().cloned().fold(0., f64::max);
+            vector = next.into_iter().map(|value| value / norm).collect();
+
+            let converged = (norm - radius).abs() < PRECISION;
+            radius = norm;
+            if converged {
+                break;
+            }
+        }
+
+        radius - 1.
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::automata::test_utils::dfa_from_regex;
+
+    #[test]
+    fn shortlex_order() {
+        let dfa = dfa_from_regex("(a|b)*c");
+        let words: Vec<String> = dfa.shortlex_words().take(7).collect();
+
+        assert_eq!(words, vec!["c", "ac", "bc", "aac", "abc", "bac", "bbc"]);
+    }
+
+    #[test]
+    fn shortlex_finite_language() {
+        let dfa = dfa_from_regex("ba|ab|a");
+        let words: Vec<String> = dfa.shortlex_words().collect();
+
+        assert_eq!(words, vec!["a", "ab", "ba"]);
+    }
+
+    #[test]
+    fn count_by_length() {
+        let dfa = dfa_from_regex("(a|b)*c");
+        assert_eq!(dfa.count_words_by_length(4), vec![0, 1, 2, 4, 8]);
+
+        let dfa = dfa_from_regex("ab|a");
+        assert_eq!(dfa.count_words_by_length(3), vec![0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn growth_rates() {
+        let approx = |a: f64, b: f64| (a - b).abs() < 1e-6;
+
+        assert!(approx(dfa_from_regex("ab|a").growth_rate(), 0.));
+        assert!(approx(dfa_from_regex("a*b*").growth_rate(), 1.));
+        assert!(approx(dfa_from_regex("(a|b)*").growth_rate(), 2.));
+
+        // fibonacci words: no two consecutive b
+        let golden_ratio = (1. + 5_f64.sqrt()) / 2.;
+        assert!(approx(dfa_from_regex("(a|ba)*").growth_rate(), golden_ratio));
+    }
+}
@@ -4,7 +4,7 @@ use egui::{
     emath::RectTransform, epaint::CubicBezierShape, Color32, Painter, Pos2, Rect, Sense, Stroke,
     Vec2,
 };
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 const ARROW_TIP_LENGHT: f32 = 10.;
 const ARROW_WIDTH: f32 = 3.;
@@ -12,6 +12,8 @@ const COLOR_EDGE: Color32 = Color32::BLUE;
 const COLOR_NODES: Color32 = Color32::WHITE;
 const COLOR_LABEL_EDGE: Color32 = Color32::GRAY;
 const COLOR_LABEL_NODE: Color32 = Color32::BLACK;
+const COLOR_HIGHLIGHT_EDGE: Color32 = Color32::RED;
+const COLOR_HIGHLIGHT_NODE: Color32 = Color32::GOLD;
 
 /// Rapresent the edges types
 pub enum EdgeType {
@@ -31,6 +33,9 @@ pub struct DisplayGraph {
     edges_type: BTreeMap<IndEdge, EdgeType>,
     explorer_order: Vec<Vec<IndNode>>,
     last_parameter: DisplayGraphParameter,
+    // nodes and edges (as pairs of nodes) drawn with the highlight colors
+    highlighted_nodes: BTreeSet<IndNode>,
+    highlighted_edges: BTreeSet<(IndNode, IndNode)>,
 }
 
 /// this struct contains, the values for drowing the graph
@@ -71,6 +76,8 @@ impl From<Graph> for DisplayGraph {
             edges_type,
             explorer_order,
             last_parameter: DisplayGraphParameter::invalid(),
+            highlighted_nodes: BTreeSet::new(),
+            highlighted_edges: BTreeSet::new(),
         };
         self_struct.set_edge_type();
         self_struct
@@ -78,6 +85,12 @@ impl From<Graph> for DisplayGraph {
 }
 
 impl DisplayGraph {
+    /// highlights the nodes, and all the edges that go from the first to the second node of a pair
+    pub fn set_highlight(&mut self, nodes: BTreeSet<IndNode>, edges: BTreeSet<(IndNode, IndNode)>) {
+        self.highlighted_nodes = nodes;
+        self.highlighted_edges = edges;
+    }
+
     /// calculate all nodes positions based on the graph traversal order (given by bfs_order)
     /// and the Display Paramters
     fn calculate_nodes_position(&mut self, bfs_max_width: f32) {
@@ -182,7 +195,7 @@ impl DisplayGraph {
             positions,
             false,
             Color32::TRANSPARENT,
-            Stroke::new(ARROW_WIDTH, COLOR_EDGE),
+            stroke,
         ));
 
         painter.line_segment([tip, tip + tip_length * (rot * dir)], stroke);
@@ -208,6 +221,11 @@ impl DisplayGraph {
         let rotation90 = egui::emath::Rot2::from_angle(std::f32::consts::PI / 8.);
         let rotation180 = egui::emath::Rot2::from_angle(std::f32::consts::PI / 2.);
         let origin = self.nodes_pos[from];
+        let stroke = if self.highlighted_edges.contains(&(*from, *to)) {
+            Stroke::new(ARROW_WIDTH, COLOR_HIGHLIGHT_EDGE)
+        } else {
+            Stroke::new(ARROW_WIDTH, COLOR_EDGE)
+        };
 
         let end = self.nodes_pos[to];
         let displacement_vec = (end - origin.to_vec2()).to_vec2();
@@ -232,7 +250,7 @@ impl DisplayGraph {
                     points,
                     false,
                     Color32::TRANSPARENT,
-                    Stroke::new(ARROW_WIDTH, COLOR_EDGE),
+                    stroke,
                 ));
             }
             EdgeType::DIRECTED => {
@@ -245,7 +263,7 @@ impl DisplayGraph {
                     painter,
                     to_screen.transform_pos(origin),
                     displacement_vec,
-                    Stroke::new(ARROW_WIDTH, COLOR_EDGE),
+                    stroke,
                 );
             }
             EdgeType::COLLIDING => {
@@ -266,7 +284,7 @@ impl DisplayGraph {
                         to_screen.transform_pos(middle),
                         to_screen.transform_pos(end),
                     ],
-                    Stroke::new(ARROW_WIDTH, COLOR_EDGE),
+                    stroke,
                 );
             }
         }
@@ -329,7 +347,12 @@ impl DisplayGraph {
         for (index, pos) in self.nodes_pos.iter() {
             let pos = to_screen.transform_pos(*pos);
 
-            painter.circle_filled(pos, self.last_parameter.node_size / 2., COLOR_NODES);
+            let color = if self.highlighted_nodes.contains(index) {
+                COLOR_HIGHLIGHT_NODE
+            } else {
+                COLOR_NODES
+            };
+            painter.circle_filled(pos, self.last_parameter.node_size / 2., color);
 
             if let Some(label) = self.graph.get_node_label(*index) {
                 painter.text(
@@ -1,11 +1,21 @@
-use crate::display::DisplayGraph;
+use eframe::egui;
+use egui::{emath, Frame, Pos2, Rect, Window};
+
+use std::collections::BTreeSet;
+
+use crate::display::{DisplayGraph, DisplayGraphParameter};
+use crate::utils::IndNode;
 
 
 /// this struct rappresent a visualizer of a graph
 /// it contains the information to show the window and display the graph
+///
+/// a visualizer can show a sequence of graphs (the steps of an algorithm),
+/// in that case the window has a slider to choose the current step.
 pub struct Visualizer {
     pub box_title: String,
-    pub graph: Option<DisplayGraph>,
+    steps: Vec<(String, DisplayGraph)>,
+    pub current_step: usize,
     pub size_node: f32,
     pub padding_y: f32,
     pub padding_x: f32,
@@ -17,8 +27,9 @@ pub struct Visualizer {
 impl Visualizer {
     pub fn new(box_title: String) -> Self {
         Self {
-            box_title: box_title,
-            graph: None,
+            box_title,
+            steps: Vec::new(),
+            current_step: 0,
             is_win_open: false,
             padding_x: 40.,
             padding_y: 40.,
@@ -27,16 +38,69 @@ impl Visualizer {
     }
 
     pub fn check_open(&mut self) {
-        if let None = self.graph {
+        if self.steps.is_empty() {
             self.is_win_open = false;
         }
         if !self.is_win_open {
-            self.graph = None;
+            self.steps.clear();
         }
     }
 
     pub fn set_graph(&mut self, graph: DisplayGraph) {
-        self.graph = Some(graph);
+        self.set_steps(vec![(String::new(), graph)]);
+    }
+
+    /// shows a sequence of graphs, each one with the description of the step
+    pub fn set_steps(&mut self, steps: Vec<(String, DisplayGraph)>) {
+        self.steps = steps;
+        self.current_step = 0;
         self.is_win_open = true;
     }
+
+    /// highlights nodes and edges of the graph of the current step
+    pub fn set_highlight(&mut self, nodes: BTreeSet<IndNode>, edges: BTreeSet<(IndNode, IndNode)>) {
+        if let Some((_, graph)) = self.steps.get_mut(self.current_step) {
+            graph.set_highlight(nodes, edges);
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        self.check_open();
+        let window = Window::new(self.box_title.clone());
+        let window = window.open(&mut self.is_win_open);
+        let window = window.scroll2([true, true]);
+        window.show(ctx, |ui| {
+            if self.steps.len() > 1 {
+                ui.add(
+                    egui::Slider::new(&mut self.current_step, 0..=self.steps.len() - 1)
+                        .text("step"),
+                );
+            }
+            if let Some((description, _)) = self.steps.get(self.current_step) {
+                if !description.is_empty() {
+                    ui.label(description);
+                }
+            }
+
+            let params = DisplayGraphParameter {
+                padding_x: self.padding_x,
+                padding_y: self.padding_y,
+                node_size: self.size_node,
+            };
+            Frame::canvas(ui.style()).show(ui, |ui| {
+                if let Some((_, tree)) = self.steps.get_mut(self.current_step) {
+                    let scren_size = tree.position(params);
+                    let (mut response, painter) =
+                        ui.allocate_painter(scren_size, egui::Sense::hover());
+
+                    let to_screen = emath::RectTransform::from_to(
+                        Rect::from_min_size(Pos2::ZERO, response.rect.size()),
+                        response.rect,
+                    );
+                    tree.drag_nodes(to_screen, ui, &mut response);
+                    tree.draw(&painter, to_screen, ui);
+                }
+            })
+        });
+    }
 }
@@ -64,4 +64,144 @@ impl std::fmt::Display for InvalidTokenError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Invalid token error: {}", self.message)
     }
-}
\ No newline at end of file
+}
+
+/// Not right linear grammar Error
+/// This error is thrown when a grammar can't be seen as a system of right linear equations,
+/// every production must be a (possibly empty) string of terminals followed by at most one non terminal.
+/// Example: A -> abB is valid
+/// Example: A -> aBb is not valid
+#[derive(Debug)]
+pub struct NotRightLinearGrammar {}
+
+impl std::error::Error for NotRightLinearGrammar {}
+
+impl std::fmt::Display for NotRightLinearGrammar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Not right linear grammar error")
+    }
+}
+
+/// Invalid homomorphism Error
+/// This error is thrown when a row of the mapping table doesn't map a single character,
+/// or when the same character is mapped twice.
+/// Example: a -> bc is valid
+/// Example: ab -> c is not valid
+#[derive(Debug)]
+pub struct InvalidHomomorphism {
+    pub symbol: String,
+}
+
+impl InvalidHomomorphism {
+    pub fn new(symbol: String) -> Self {
+        Self { symbol }
+    }
+}
+
+impl std::error::Error for InvalidHomomorphism {}
+
+impl std::fmt::Display for InvalidHomomorphism {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid homomorphism error: {:?} must be a single character mapped once", self.symbol)
+    }
+}
+
+/// Invalid sample Error
+/// This error is thrown when a line of a sample set is not a labelled word,
+/// or when a word is labelled both as positive and negative.
+/// Example: + ab is valid
+/// Example: ab is not valid
+#[derive(Debug)]
+pub struct InvalidSample {
+    pub line: usize,
+    pub content: String,
+}
+
+impl InvalidSample {
+    pub fn new(line: usize, content: String) -> Self {
+        Self { line, content }
+    }
+}
+
+impl std::error::Error for InvalidSample {}
+
+impl std::fmt::Display for InvalidSample {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid sample error at line {}: {}", self.line, self.content)
+    }
+}
+
+/// Grammar syntax Error
+/// This error is thrown when the text of a grammar can't be parsed,
+/// line and column (both starting from 1) point to the wrong symbol.
+/// Example: E -> E + T | T is valid
+/// Example: E E + T is not valid
+#[derive(Debug, PartialEq)]
+pub struct GrammarSyntaxError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl GrammarSyntaxError {
+    pub fn new(line: usize, column: usize, message: String) -> Self {
+        Self { line, column, message }
+    }
+}
+
+impl std::error::Error for GrammarSyntaxError {}
+
+impl std::fmt::Display for GrammarSyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Grammar syntax error at line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Unknown Terminal Error
+/// This error is thrown when a word of the input of a parser is not a terminal of the grammar.
+/// Example: id + id is valid for the expression grammar
+/// Example: id - id is not valid
+#[derive(Debug)]
+pub struct UnknownTerminal {
+    pub name: String,
+}
+
+impl UnknownTerminal {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl std::error::Error for UnknownTerminal {}
+
+impl std::fmt::Display for UnknownTerminal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown terminal error: {} is not a terminal of the grammar", self.name)
+    }
+}
+
+/// Parser Conflict Error
+/// This error is thrown when a deterministic parser is run on a table with conflicts,
+/// kind is the kind of the table, like LL(1).
+#[derive(Debug)]
+pub struct ParserConflict {
+    pub kind: String,
+}
+
+impl ParserConflict {
+    pub fn new(kind: String) -> Self {
+        Self { kind }
+    }
+}
+
+impl std::error::Error for ParserConflict {}
+
+impl std::fmt::Display for ParserConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Parser conflict error: the grammar is not {}, the table has conflicts", self.kind)
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::automata::{ReOperator, EPSILON};
+use crate::error::NotRightLinearGrammar;
+use crate::grammar::{self, Grammar, Letter, NonTerminal};
+
+/// right linear equation X = c1 X1 | ... | cn Xn | constant
+/// a missing coefficient or constant is the empty language
+#[derive(Debug, Clone, PartialEq)]
+struct Equation {
+    coefficients: BTreeMap<NonTerminal, ReOperator>,
+    constant: Option<ReOperator>,
+}
+
+/// joins two optional languages with an or, None is the empty language
+fn or(first: Option<ReOperator>, second: ReOperator) -> ReOperator {
+    match first {
+        Some(first) => ReOperator::Or(Box::new(first), Box::new(second)).simplify(),
+        None => second,
+    }
+}
+
+fn concat(first: &ReOperator, second: &ReOperator) -> ReOperator {
+    ReOperator::Concat(Box::new(first.clone()), Box::new(second.clone())).simplify()
+}
+
+impl Equation {
+    fn new() -> Self {
+        Self {
+            coefficients: BTreeMap::new(),
+            constant: None,
+        }
+    }
+
+    fn add_term(&mut self, coefficient: ReOperator, variable: Option<NonTerminal>) {
+        match variable {
+            Some(variable) => {
+                let old = self.coefficients.remove(&variable);
+                self.coefficients.insert(variable, or(old, coefficient));
+            }
+            None => self.constant = Some(or(self.constant.take(), coefficient)),
+        }
+    }
+
+    /// Arden's lemma: X = AX | B has the solution X = A*B,
+    /// the coefficient of the variable itself is removed
+    fn apply_arden(&mut self, variable: NonTerminal) {
+        if let Some(coefficient) = self.coefficients.remove(&variable) {
+            let star = ReOperator::KleeneStar(Box::new(coefficient));
+            for other in self.coefficients.values_mut() {
+                *other = concat(&star, other);
+            }
+            self.constant = self
+                .constant
+                .as_ref()
+                .map(|constant| concat(&star, constant));
+        }
+    }
+
+    /// replaces the variable with the right hand side of its equation,
+    /// which must not contain the variable itself
+    fn substitute(&mut self, variable: NonTerminal, equation: &Equation) {
+        if let Some(coefficient) = self.coefficients.remove(&variable) {
+            for (other, other_coefficient) in equation.coefficients.iter() {
+                self.add_term(concat(&coefficient, other_coefficient), Some(*other));
+            }
+            if let Some(constant) = &equation.constant {
+                self.add_term(concat(&coefficient, constant), None);
+            }
+        }
+    }
+
+    /// shows the equation with the names of the non terminals as variables
+    fn to_string_with(&self, names: &[String]) -> String {
+        let mut terms = vec![];
+        for (variable, coefficient) in self.coefficients.iter() {
+            let name = &names[*variable];
+            match coefficient {
+                ReOperator::Char(EPSILON) => terms.push(name.to_string()),
+                ReOperator::Or(_, _) => terms.push(format!("({}){}", coefficient, name)),
+                _ => terms.push(format!("{}{}", coefficient, name)),
+            }
+        }
+        if let Some(constant) = &self.constant {
+            terms.push(constant.to_string());
+        }
+
+        if terms.is_empty() {
+            "∅".to_string()
+        } else {
+            terms.join(" | ")
+        }
+    }
+}
+
+/// System of right linear equations, one for each non terminal of a right linear grammar.
+/// The language generated by the non terminal X is the solution of its equation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquationSystem {
+    start_variable: NonTerminal,
+    equations: BTreeMap<NonTerminal, Equation>,
+    // names of the non terminals of the grammar, used for the variables
+    names: Vec<String>,
+}
+
+impl EquationSystem {
+    /// builds the system of a right linear grammar, every production must be a string
+    /// of terminals followed by at most one non terminal, like the ones of `Grammar::from(&DFA)`.
+    /// The terminals must be single characters, the letters of the regex
+    pub fn from_grammar(grammar: &Grammar) -> Result<Self, Box<dyn Error>> {
+        let mut equations = BTreeMap::new();
+        equations.insert(grammar.get_start_symbol(), Equation::new());
+
+        for production in grammar.get_productions() {
+            let mut coefficient = ReOperator::Char(EPSILON);
+            let mut variable = None;
+            for letter in production.get_rhs() {
+                if variable.is_some() {
+                    return Err(Box::new(NotRightLinearGrammar {}));
+                }
+                match letter {
+                    Letter::Terminal(grammar::EPSILON) => {}
+                    Letter::Terminal(terminal) => {
+                        let mut chars = grammar.get_symbols().get_terminal_name(*terminal).chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(ch), None) => {
+                                coefficient = concat(&coefficient, &ReOperator::Char(ch))
+                            }
+                            _ => return Err(Box::new(NotRightLinearGrammar {})),
+                        }
+                    }
+                    Letter::NonTerminal(non_terminal) => variable = Some(*non_terminal),
+                }
+            }
+
+            if let Some(variable) = variable {
+                equations.entry(variable).or_insert_with(Equation::new);
+            }
+            equations
+                .entry(production.get_lhs())
+                .or_insert_with(Equation::new)
+                .add_term(coefficient, variable);
+        }
+
+        let names = (0..grammar.get_symbols().get_num_non_terminals())
+            .map(|non_terminal| grammar.non_terminal_to_string(non_terminal))
+            .collect();
+
+        Ok(Self {
+            start_variable: grammar.get_start_symbol(),
+            equations,
+            names,
+        })
+    }
+
+    /// solves the system eliminating one variable at a time: first Arden's lemma removes
+    /// the variable from its own equation, then its solution is substituted in the others.
+    /// The start variable is solved last.
+    ///
+    /// returns the regex of the start variable (None if the language is empty) together
+    /// with the description of each step
+    pub fn solve(mut self) -> (Option<ReOperator>, Vec<String>) {
+        let mut steps = vec![format!("initial system:\n{}", self)];
+
+        let variables: Vec<NonTerminal> = self
+            .equations
+            .keys()
+            .cloned()
+            .filter(|variable| *variable != self.start_variable)
+            .collect();
+
+        for variable in variables {
+            let name = &self.names[variable];
+            let mut equation = self.equations.remove(&variable).unwrap();
+            if equation.coefficients.contains_key(&variable) {
+                equation.apply_arden(variable);
+                steps.push(format!(
+                    "Arden's lemma on {}: {} = {}",
+                    name,
+                    name,
+                    equation.to_string_with(&self.names)
+                ));
+            }
+
+            for other in self.equations.values_mut() {
+                other.substitute(variable, &equation);
+            }
+            steps.push(format!(
+                "substitute {} = {}:\n{}",
+                name,
+                equation.to_string_with(&self.names),
+                self
+            ));
+        }
+
+        let start = self.start_variable;
+        let name = &self.names[start];
+        let equation = self.equations.get_mut(&start).unwrap();
+        if equation.coefficients.contains_key(&start) {
+            equation.apply_arden(start);
+            steps.push(format!(
+                "Arden's lemma on {}: {} = {}",
+                name,
+                name,
+                equation.to_string_with(&self.names)
+            ));
+        }
+
+        (equation.constant.clone(), steps)
+    }
+}
+
+impl fmt::Display for EquationSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self
+            .equations
+            .iter()
+            .map(|(variable, equation)| {
+                format!(
+                    "{} = {}",
+                    self.names[*variable],
+                    equation.to_string_with(&self.names)
+                )
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::automata::test_utils::assert_same_language;
+    use crate::automata::DFA;
+    use crate::map;
+
+    #[test]
+    fn solve_dfa_equations() {
+        for str in ["a(b|c)*", "(ab|ba)*a", "a*b*", "(a|b)*abb", "ε|ab"] {
+            let regex = ReOperator::from_string(&str.to_string()).unwrap();
+            let dfa = DFA::from(&regex).get_minimized_dfa();
+
+            let system = EquationSystem::from_grammar(&Grammar::from(&dfa)).unwrap();
+            let (result, _) = system.solve();
+
+            assert_same_language(&regex, &result.unwrap());
+        }
+    }
+
+    #[test]
+    fn empty_language() {
+        // the final state can't be reached
+        let dfa: DFA<usize> = DFA::from_state(
+            2,
+            0,
+            vec![1],
+            vec![map! { 'a' => 0 }, map! { 'a' => 1 }],
+            None,
+        );
+
+        let system = EquationSystem::from_grammar(&Grammar::from(&dfa)).unwrap();
+        assert_eq!(system.solve().0, None);
+    }
+
+    #[test]
+    fn system_display() {
+        // X0 = aX1, X1 = bX1 | ε
+        let dfa: DFA<usize> = DFA::from_state(
+            2,
+            0,
+            vec![1],
+            vec![map! { 'a' => 1 }, map! { 'b' => 1 }],
+            None,
+        );
+
+        let system = EquationSystem::from_grammar(&Grammar::from(&dfa)).unwrap();
+        assert_eq!(system.to_string(), "X0 = aX1\nX1 = bX1 | ε");
+
+        let (result, steps) = system.solve();
+        assert_eq!(result.unwrap().to_string(), "ab*");
+        assert_eq!(steps[1], "Arden's lemma on X1: X1 = b*");
+    }
+
+    #[test]
+    fn grammar_names() {
+        let grammar = Grammar::from_text("S -> a S | b A\nA -> c A | ε").unwrap();
+
+        let system = EquationSystem::from_grammar(&grammar).unwrap();
+        assert_eq!(system.to_string(), "S = aS | bA\nA = cA | ε");
+
+        let (result, steps) = system.solve();
+        assert_eq!(steps[1], "Arden's lemma on A: A = c*");
+        assert_same_language(
+            &result.unwrap(),
+            &ReOperator::from_string(&"a*bc*".to_string()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn multi_character_terminal() {
+        let grammar = Grammar::from_text("S -> ab S | c").unwrap();
+
+        let error = EquationSystem::from_grammar(&grammar).unwrap_err();
+        assert!(error.downcast_ref::<NotRightLinearGrammar>().is_some());
+    }
+}
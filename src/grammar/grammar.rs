@@ -1,9 +1,12 @@
 use std::collections::{BTreeSet, BTreeMap};
+use std::fmt;
 
 use crate::automata::DFA;
+use crate::grammar::SymbolTable;
 
+/// compact ids of the symbols, their names are in the `SymbolTable` of the grammar
 pub type NonTerminal = usize;
-pub type Terminal = char;
+pub type Terminal = usize;
 
 #[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord)]
 pub enum Letter {
@@ -11,164 +14,180 @@ pub enum Letter {
     Terminal(Terminal),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Production {
     lhs: NonTerminal,
     rhs: Vec<Letter>,
 }
 
-#[derive(Debug, PartialEq)]
+/// LR(0) item, the production with index `production` with a dot before
+/// the letter at index `dot` of its right hand side.
+/// The ε productions have only the item with the dot at 0.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Item {
+    pub production: usize,
+    pub dot: usize,
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct Grammar {
     start_symbol: NonTerminal,
     productions: Vec<Production>,
+    symbols: SymbolTable,
 
-    // NOTE: the nullable non terminals could be cached in a Option<BTreeSet<NonTerminal>>
-    // but we need to assume that the grammar once created is immutable, or we need to update
-    // this option after each update to the grammar
-    nullable: Option<BTreeSet<NonTerminal>>,
+    // NOTE: the FIRST and FOLLOW tables are cached, we need to assume that the grammar
+    // once created is immutable, or we need to invalidate them after each update to the grammar
+    first: Option<BTreeMap<NonTerminal, BTreeSet<Terminal>>>,
+    follow: Option<BTreeMap<NonTerminal, BTreeSet<Terminal>>>,
 }
 
-const EPSILON: char = 'ε';
-const STRING_END: char = '$';
+// reserved terminal ids, see `SymbolTable`
+pub const EPSILON: Terminal = 0;
+pub const STRING_END: Terminal = 1;
+
+impl Production {
+    pub fn new(lhs: NonTerminal, rhs: Vec<Letter>) -> Self {
+        Self { lhs, rhs }
+    }
 
-// NOTE: this could be in conflict with the Terminal symbols, so
-// it is MANDATORY that the Terminal doesn´t have dots in it!
-const ITEM_SEP: char = '.';
+    pub fn get_lhs(&self) -> NonTerminal {
+        self.lhs
+    }
+
+    pub fn get_rhs(&self) -> &Vec<Letter> {
+        &self.rhs
+    }
+}
 
 impl Grammar {
-    pub fn first(&mut self, letter: &Letter) -> BTreeSet<Terminal> {
-        if let None = self.nullable {
-            self.nullable = Some(self.get_nullable());
+    pub fn new(start_symbol: NonTerminal, productions: Vec<Production>, symbols: SymbolTable) -> Self {
+        Self {
+            start_symbol,
+            productions,
+            symbols,
+            first: None,
+            follow: None,
         }
+    }
 
-        match letter {
-            Letter::NonTerminal(non_terminal) => {
-                let mut first = self._first(non_terminal);
+    pub fn get_symbols(&self) -> &SymbolTable {
+        &self.symbols
+    }
 
-                if self.nullable.as_ref().unwrap().contains(&non_terminal) {
-                    first.insert(EPSILON);
-                }
+    pub fn get_start_symbol(&self) -> NonTerminal {
+        self.start_symbol
+    }
 
-                first
-            }
-            Letter::Terminal(terminal) => {
-                let mut first = BTreeSet::new();
-                first.insert(*terminal);
-                first
-            }
-        }
+    pub fn get_productions(&self) -> &Vec<Production> {
+        &self.productions
     }
 
-    fn _first(&self, non_terminal: &NonTerminal) -> BTreeSet<Terminal> {
-        let nullable = self.nullable.as_ref().unwrap();
-        let mut first = BTreeSet::new();
+    /// returns FIRST of the letter, it contains ε if the letter is nullable
+    pub fn first(&mut self, letter: &Letter) -> BTreeSet<Terminal> {
+        self.first_of_sequence(std::slice::from_ref(letter))
+    }
 
-        for production in self.productions.iter() {
-            if production.lhs != *non_terminal {
-                continue;
-            }
-            
-            for letter in production.rhs.iter() {
-                // we can continue to add more only if previous symbols are nullable
-                match letter {
-                    Letter::NonTerminal(idx) => {
-                        first.append(&mut self._first(idx));
-                        if !nullable.contains(idx) {
-                            break;
-                        }
-                    },
-                    Letter::Terminal(ch) => {
-                        // NOTE: i don't want to insert epsilons, because
-                        // then i should remove them in upper level!
-                        if *ch != EPSILON {
-                            first.insert(*ch);
-                            break;
-                        }
+    /// returns FIRST of the sequence of letters, the terminals that can start a string
+    /// derived from it. It contains ε if all the letters are nullable
+    pub fn first_of_sequence(&mut self, letters: &[Letter]) -> BTreeSet<Terminal> {
+        Self::sequence_first(self.get_first_table(), letters)
+    }
+
+    fn sequence_first(
+        first_table: &BTreeMap<NonTerminal, BTreeSet<Terminal>>,
+        letters: &[Letter],
+    ) -> BTreeSet<Terminal> {
+        let mut first = BTreeSet::new();
+        for letter in letters.iter() {
+            match letter {
+                Letter::Terminal(EPSILON) => {}
+                Letter::Terminal(terminal) => {
+                    first.insert(*terminal);
+                    return first;
+                }
+                Letter::NonTerminal(non_terminal) => {
+                    let letter_first = &first_table[non_terminal];
+                    first.extend(letter_first.iter().filter(|terminal| **terminal != EPSILON));
+                    // we can continue only if the non terminal is nullable
+                    if !letter_first.contains(&EPSILON) {
+                        return first;
                     }
                 }
             }
         }
 
+        first.insert(EPSILON);
         first
     }
 
-    pub fn follow(&mut self, non_terminal: &NonTerminal) -> BTreeSet<Terminal> {
-        if let None = self.nullable {
-            self.nullable = Some(self.get_nullable());
+    /// returns FIRST of every non terminal, computed as a fixpoint: the FIRST of
+    /// each right hand side is added to its non terminal until nothing changes
+    pub fn get_first_table(&mut self) -> &BTreeMap<NonTerminal, BTreeSet<Terminal>> {
+        if self.first.is_none() {
+            let mut first_table = self.empty_table();
+
+            let mut has_changed = true;
+            while has_changed {
+                has_changed = false;
+                for production in self.productions.iter() {
+                    let first = Self::sequence_first(&first_table, &production.rhs);
+                    let lhs_first = first_table.get_mut(&production.lhs).unwrap();
+                    let len = lhs_first.len();
+                    lhs_first.extend(first);
+                    has_changed |= lhs_first.len() != len;
+                }
+            }
+            self.first = Some(first_table);
         }
 
-        let num_non_terminal = self.get_non_terminal().len();
-        let mut used = vec![0; num_non_terminal];
-
-        self._follow(non_terminal, &mut used)
+        self.first.as_ref().unwrap()
     }
 
-    fn _follow(&self, non_terminal: &NonTerminal, used: &mut Vec<usize>) -> BTreeSet<Terminal> {
-        if used[*non_terminal] == 1 {
-            return BTreeSet::new();
-        }
-        used[*non_terminal] = 1;
-
-        let nullable = self.nullable.as_ref().unwrap();
-        let mut follow = BTreeSet::new();
-
-        if *non_terminal == self.start_symbol {
-            follow.insert(STRING_END);
-        }
-
-        for production in self.productions.iter() {
-            for (i, letter) in production.rhs.iter().enumerate() {
-                match letter {
-                    Letter::NonTerminal(idx) => {
-                        if *idx != *non_terminal {
-                            continue;
-                        }
-
-                        // if we are at the end of the production, then we need to add the follow of the lhs
-                        if i == production.rhs.len() - 1 {
-                            follow.append(&mut self._follow(&production.lhs, used));
-                        } else {
-                            // otherwise we need to add the first of the next symbol
-                            let next_letter = &production.rhs[i + 1];
-                            match next_letter {
-                                Letter::NonTerminal(idx) => {
-                                    follow.append(&mut self._first(idx));
-                                },
-                                Letter::Terminal(ch) => {
-                                    follow.insert(*ch);
-                                }
-                            }
+    /// returns a table with an empty set for each non terminal
+    fn empty_table(&self) -> BTreeMap<NonTerminal, BTreeSet<Terminal>> {
+        (0..self.symbols.get_num_non_terminals())
+            .map(|non_terminal| (non_terminal, BTreeSet::new()))
+            .collect()
+    }
 
-                            // if the whole next symbol is nullable, then we need to add the follow of the lhs
-                            let mut is_nullable = true;
-                            for letter in production.rhs[i + 1..].iter() {
-                                match letter {
-                                    Letter::NonTerminal(idx) => {
-                                        if !nullable.contains(idx) {
-                                            is_nullable = false;
-                                            break;
-                                        }
-                                    },
-                                    Letter::Terminal(ch) => {
-                                        if *ch != EPSILON {
-                                            is_nullable = false;
-                                            break;
-                                        }
-                                    }
-                                }
-                            }
+    pub fn follow(&mut self, non_terminal: &NonTerminal) -> BTreeSet<Terminal> {
+        self.get_follow_table()[non_terminal].clone()
+    }
 
-                            if is_nullable {
-                                follow.append(&mut self._follow(&production.lhs, used));
-                            }
+    /// returns FOLLOW of every non terminal, computed as a fixpoint: $ follows the start
+    /// symbol, and for each production A -> α B β FOLLOW(B) contains FIRST(β) without ε,
+    /// and all FOLLOW(A) if β is nullable
+    pub fn get_follow_table(&mut self) -> &BTreeMap<NonTerminal, BTreeSet<Terminal>> {
+        if self.follow.is_none() {
+            let first_table = self.get_first_table().clone();
+            let mut follow_table = self.empty_table();
+            follow_table.get_mut(&self.start_symbol).unwrap().insert(STRING_END);
+
+            let mut has_changed = true;
+            while has_changed {
+                has_changed = false;
+                for production in self.productions.iter() {
+                    for (i, letter) in production.rhs.iter().enumerate() {
+                        let non_terminal = match letter {
+                            Letter::NonTerminal(non_terminal) => *non_terminal,
+                            Letter::Terminal(_) => continue,
+                        };
+
+                        let mut follow = Self::sequence_first(&first_table, &production.rhs[i + 1..]);
+                        if follow.remove(&EPSILON) {
+                            follow.extend(follow_table[&production.lhs].iter().cloned());
                         }
-                    },
-                    Letter::Terminal(_) => {}
+                        let non_terminal_follow = follow_table.get_mut(&non_terminal).unwrap();
+                        let len = non_terminal_follow.len();
+                        non_terminal_follow.extend(follow);
+                        has_changed |= non_terminal_follow.len() != len;
+                    }
                 }
             }
+            self.follow = Some(follow_table);
         }
 
-        follow
+        self.follow.as_ref().unwrap()
     }
 
     pub fn get_non_terminal(&self) -> BTreeSet<NonTerminal> {
@@ -294,8 +313,7 @@ impl Grammar {
             })
         });
 
-        // invalidate nullable
-        self.nullable = None;
+        self.invalidate_sets();
     }
 
     pub fn get_unitary_couples(&self) -> BTreeSet<(NonTerminal, NonTerminal)>  {
@@ -351,14 +369,16 @@ impl Grammar {
             }
         });
 
-        // add corresponding productions 
-        let mut adj_list = self.transitions_to_adj_list();
+        // add corresponding productions, the couples are transitive so the
+        // non unitary productions of the second element are enough
+        let non_unitary = self.transitions_to_adj_list();
+        let mut adj_list = non_unitary.clone();
         for unitary_couple in unitary_couples.iter() {
             if unitary_couple.0 == unitary_couple.1 {
                 continue;
             }
 
-            let mut to_insert = adj_list.get(&unitary_couple.1).unwrap().clone();
+            let mut to_insert = non_unitary.get(&unitary_couple.1).cloned().unwrap_or_default();
 
             adj_list.entry(unitary_couple.0)
                 .or_insert(BTreeSet::new())
@@ -377,8 +397,77 @@ impl Grammar {
         }
         self.productions = new_transitions;
 
-        // invalidate nullable
-        self.nullable = None;
+        self.invalidate_sets();
+    }
+
+    /// removes the ε productions: every production A -> α is replaced by all the
+    /// variants of α with some of its nullable non terminals omitted, except the empty one
+    /// and A -> A. The non terminals left without productions (A -> ε only) are dropped
+    /// with the productions that use them.
+    /// If the start symbol S is nullable, the new start symbol S' has the productions
+    /// S' -> S | ε, so the grammar still generates ε
+    pub fn remove_epsilon(&mut self) {
+        let nullable = self.get_nullable();
+
+        let mut productions: Vec<Production> = vec![];
+        for production in self.productions.iter() {
+            if production.is_epsilon() {
+                continue;
+            }
+
+            // built from the last letter, so the variants omitting the first
+            // nullable letters come first, repeated variants are skipped right away
+            let mut variants: Vec<Vec<Letter>> = vec![vec![]];
+            for letter in production.rhs.iter().rev() {
+                let is_nullable = matches!(letter, Letter::NonTerminal(idx) if nullable.contains(idx));
+                let mut next_variants = vec![];
+                for variant in variants {
+                    let mut with_letter = vec![letter.clone()];
+                    with_letter.extend(variant.iter().cloned());
+                    if !next_variants.contains(&with_letter) {
+                        next_variants.push(with_letter);
+                    }
+                    if is_nullable && !next_variants.contains(&variant) {
+                        next_variants.push(variant);
+                    }
+                }
+                variants = next_variants;
+            }
+
+            for rhs in variants {
+                let variant = Production { lhs: production.lhs, rhs };
+                let is_self_loop = variant.rhs == [Letter::NonTerminal(variant.lhs)];
+                if !variant.rhs.is_empty() && !is_self_loop && !productions.contains(&variant) {
+                    productions.push(variant);
+                }
+            }
+        }
+
+        if nullable.contains(&self.start_symbol) {
+            let name = self.symbols.get_non_terminal_name(self.start_symbol).to_string();
+            let start = self.add_fresh_non_terminal(&name);
+            productions.push(Production { lhs: start, rhs: vec![Letter::NonTerminal(self.start_symbol)] });
+            productions.push(Production { lhs: start, rhs: vec![Letter::Terminal(EPSILON)] });
+            self.start_symbol = start;
+        }
+
+        // dropping a production can leave another non terminal without productions
+        loop {
+            let defined: BTreeSet<NonTerminal> = productions.iter().map(|production| production.lhs).collect();
+            let num_productions = productions.len();
+            productions.retain(|production| {
+                production.rhs.iter().all(|letter| match letter {
+                    Letter::NonTerminal(idx) => defined.contains(idx),
+                    Letter::Terminal(_) => true,
+                })
+            });
+            if productions.len() == num_productions {
+                break;
+            }
+        }
+        self.productions = productions;
+
+        self.invalidate_sets();
     }
 
     fn transitions_to_adj_list(&self) -> BTreeMap<NonTerminal, BTreeSet<Vec<Letter>>> {
@@ -392,29 +481,141 @@ impl Grammar {
         adj_list
     }
 
-    pub fn get_itemization(&self) -> Vec<Production> {
-        let mut itemized_transitions = vec![];
-        for production in self.productions.iter() {
-            if production.rhs.len() == 1 && production.rhs[0] == Letter::Terminal(EPSILON) {
-                itemized_transitions.push(Production {
-                    lhs: production.lhs,
-                    rhs: vec![Letter::Terminal(ITEM_SEP)]
-                });
+    /// clears the cached FIRST and FOLLOW tables after a change to the productions
+    fn invalidate_sets(&mut self) {
+        self.first = None;
+        self.follow = None;
+    }
+
+    /// returns all the LR(0) items of the grammar
+    pub fn get_itemization(&self) -> Vec<Item> {
+        let mut items = vec![];
+        for (index, production) in self.productions.iter().enumerate() {
+            if production.is_epsilon() {
+                items.push(Item { production: index, dot: 0 });
                 continue;
             }
 
-            for i in 0..=production.rhs.len() {
-                let mut rhs = production.rhs.clone();
-
-                rhs.insert(i, Letter::Terminal(ITEM_SEP));
-                itemized_transitions.push(Production {
-                    lhs: production.lhs,
-                    rhs: rhs
-                });
+            for dot in 0..=production.rhs.len() {
+                items.push(Item { production: index, dot });
             }
         }
 
-        itemized_transitions
+        items
+    }
+
+    pub fn letter_to_string(&self, letter: &Letter) -> String {
+        match letter {
+            Letter::NonTerminal(non_terminal) => self.non_terminal_to_string(*non_terminal),
+            Letter::Terminal(terminal) => self.terminal_to_string(*terminal),
+        }
+    }
+
+    pub fn non_terminal_to_string(&self, non_terminal: NonTerminal) -> String {
+        self.symbols.get_non_terminal_name(non_terminal).to_string()
+    }
+
+    /// returns the name of the terminal, between quotes if it could be read
+    /// as something else: a non terminal, ε, the end of the string $ or a special symbol
+    pub fn terminal_to_string(&self, terminal: Terminal) -> String {
+        let name = self.symbols.get_terminal_name(terminal);
+        if terminal == EPSILON || terminal == STRING_END {
+            return name.to_string();
+        }
+
+        let is_special = name.contains(|ch: char| ch.is_whitespace() || "|#'\"→".contains(ch))
+            || name.contains("->")
+            || name.contains("::=")
+            || name == "ε"
+            || name == "$"
+            || self.symbols.get_non_terminal(name).is_some();
+        if !is_special {
+            name.to_string()
+        } else if name.contains('\'') {
+            format!("\"{}\"", name)
+        } else {
+            format!("'{}'", name)
+        }
+    }
+
+    /// shows the production with the given index as "A -> a B"
+    pub fn production_to_string(&self, production: usize) -> String {
+        let production = &self.productions[production];
+        let rhs: Vec<String> = production.rhs.iter().map(|letter| self.letter_to_string(letter)).collect();
+        format!("{} -> {}", self.symbols.get_non_terminal_name(production.lhs), rhs.join(" "))
+    }
+
+    /// returns the letter after the dot of the item, None if the item is complete
+    pub fn get_next_letter(&self, item: &Item) -> Option<&Letter> {
+        let production = &self.productions[item.production];
+        if production.is_epsilon() {
+            return None;
+        }
+        production.rhs.get(item.dot)
+    }
+
+    /// returns a copy of the grammar with the new start symbol S' and the production S' -> S
+    /// added after the others, so the indexes of the old productions don't change
+    pub fn get_augmented(&self) -> Grammar {
+        let mut augmented = self.clone();
+        let start = augmented.add_fresh_non_terminal(self.symbols.get_non_terminal_name(self.start_symbol));
+        augmented.productions.push(Production::new(start, vec![Letter::NonTerminal(self.start_symbol)]));
+        augmented.start_symbol = start;
+        augmented.invalidate_sets();
+        augmented
+    }
+
+    /// adds a non terminal named like the given one followed by enough ' to be new
+    fn add_fresh_non_terminal(&mut self, name: &str) -> NonTerminal {
+        let mut name = format!("{}'", name);
+        while self.symbols.get_non_terminal(&name).is_some() {
+            name.push('\'');
+        }
+        self.symbols.add_non_terminal(&name)
+    }
+
+    /// shows the item as "A -> a . B"
+    pub fn item_to_string(&self, item: &Item) -> String {
+        let production = &self.productions[item.production];
+        let mut letters: Vec<String> = if production.is_epsilon() {
+            vec![]
+        } else {
+            production.rhs.iter().map(|letter| self.letter_to_string(letter)).collect()
+        };
+        letters.insert(item.dot, ".".to_string());
+
+        format!(
+            "{} -> {}",
+            self.symbols.get_non_terminal_name(production.lhs),
+            letters.join(" ")
+        )
+    }
+}
+
+impl Production {
+    /// returns true for the productions A -> ε
+    pub fn is_epsilon(&self) -> bool {
+        self.rhs.len() == 1 && self.rhs[0] == Letter::Terminal(EPSILON)
+    }
+}
+
+impl fmt::Display for Grammar {
+    /// one line for each non terminal with all its alternatives, like "E -> E + T | T".
+    /// The output can be read back by `Grammar::from_text`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut alternatives: BTreeMap<NonTerminal, Vec<String>> = BTreeMap::new();
+        for production in self.productions.iter() {
+            let rhs: Vec<String> = production.rhs.iter().map(|letter| self.letter_to_string(letter)).collect();
+            alternatives.entry(production.lhs).or_default().push(rhs.join(" "));
+        }
+
+        // the start symbol first, so it's still the start symbol when parsed
+        let mut lines = vec![];
+        let start = alternatives.remove_entry(&self.start_symbol);
+        for (lhs, rhs) in start.into_iter().chain(alternatives) {
+            lines.push(format!("{} -> {}", self.symbols.get_non_terminal_name(lhs), rhs.join(" | ")));
+        }
+        write!(f, "{}", lines.join("\n"))
     }
 }
 
@@ -424,11 +625,17 @@ impl<T> From<&DFA<T>> for Grammar {
         // internal representation tightly coupled, but this implementation is much simpler
 
         let mut productions = vec![];
+        let mut symbols = SymbolTable::new();
+        // the ids of the non terminals are the states
+        for state in 0..dfa.get_num_states() {
+            symbols.add_non_terminal(&format!("X{}", state));
+        }
 
         for (idx, transitions) in dfa.get_transitions().iter().enumerate() {
             for (transition_ch, dest) in transitions.iter() {
                 let lhs = idx;
-                let rhs = vec![Letter::Terminal(*transition_ch), Letter::NonTerminal(*dest)];
+                let terminal = symbols.add_terminal(&transition_ch.to_string());
+                let rhs = vec![Letter::Terminal(terminal), Letter::NonTerminal(*dest)];
                 productions.push(Production { lhs: lhs, rhs });
             }
         }
@@ -442,8 +649,10 @@ impl<T> From<&DFA<T>> for Grammar {
         Self { 
             start_symbol: dfa.get_start_state(),
             productions,
+            symbols,
 
-            nullable: None,
+            first: None,
+            follow: None,
         }
     }
 }
@@ -451,35 +660,23 @@ impl<T> From<&DFA<T>> for Grammar {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::grammar::test_utils::{expression_grammar, ll_expression_grammar, non_terminal, terminal};
     use crate::map;
 
     fn get_test_grammar() -> Grammar {
-        // S -> Ab | c
-        // A -> aA | ε
-        // S = 0
-        // A = 1
-        Grammar {
-            start_symbol: 0,
-            productions: vec![
-                Production { lhs: 0, rhs: vec![Letter::NonTerminal(1), Letter::Terminal('b')] },
-                Production { lhs: 0, rhs: vec![Letter::Terminal('c')] },
-                Production { lhs: 1, rhs: vec![Letter::Terminal('a'), Letter::NonTerminal(1)] },
-                Production { lhs: 1, rhs: vec![Letter::Terminal(EPSILON)] },
-            ],
-            nullable: None,
-        }
+        Grammar::from_text("S -> A b | c\nA -> a A | ε").unwrap()
     }
 
     #[test]
     fn test_first() {
         let mut grammar = get_test_grammar();
 
-        let first = grammar.first(&Letter::NonTerminal(0));
+        let first = grammar.first(&Letter::NonTerminal(non_terminal(&grammar, "S")));
 
         assert_eq!(first.len(), 3);
-        assert!(first.contains(&'a'));
-        assert!(first.contains(&'b'));
-        assert!(first.contains(&'c'));
+        assert!(first.contains(&terminal(&grammar, "a")));
+        assert!(first.contains(&terminal(&grammar, "b")));
+        assert!(first.contains(&terminal(&grammar, "c")));
 
     }
 
@@ -487,13 +684,13 @@ mod test {
     fn test_follow() {
         let mut grammar = get_test_grammar();
 
-        let follow = grammar.follow(&0);
+        let follow = grammar.follow(&non_terminal(&grammar, "S"));
         assert_eq!(follow.len(), 1);
         assert!(follow.contains(&STRING_END));
 
-        let follow = grammar.follow(&1);
+        let follow = grammar.follow(&non_terminal(&grammar, "A"));
         assert_eq!(follow.len(), 1);
-        assert!(follow.contains(&'b'));
+        assert!(follow.contains(&terminal(&grammar, "b")));
     }
 
     #[test]
@@ -502,7 +699,7 @@ mod test {
 
         let nullable = grammar.get_nullable();
         assert_eq!(nullable.len(), 1);
-        assert!(nullable.contains(&1));
+        assert!(nullable.contains(&non_terminal(&grammar, "A")));
     }
 
     #[test]
@@ -530,96 +727,72 @@ mod test {
         );
 
         let grammar = Grammar::from(&dfa);
+        let (a, b) = (terminal(&grammar, "a"), terminal(&grammar, "b"));
 
         // FIXME: the order in the production matters, but it shouldn't be the case.
-        let result = Grammar {
-            start_symbol: 0,
-            productions: vec![
-                Production { lhs: 0, rhs: vec![Letter::Terminal('a'), Letter::NonTerminal(2)] },
-                Production { lhs: 0, rhs: vec![Letter::Terminal('b'), Letter::NonTerminal(1)] },
-                Production { lhs: 1, rhs: vec![Letter::Terminal('a'), Letter::NonTerminal(1)] },
-                Production { lhs: 1, rhs: vec![Letter::Terminal('b'), Letter::NonTerminal(2)] },
-                Production { lhs: 2, rhs: vec![Letter::Terminal('a'), Letter::NonTerminal(2)] },
-                Production { lhs: 2, rhs: vec![Letter::Terminal('b'), Letter::NonTerminal(2)] },
-                Production { lhs: 1, rhs: vec![Letter::Terminal(EPSILON)] },
-            ],
-            nullable: None,
-        };
+        let productions = vec![
+            Production { lhs: 0, rhs: vec![Letter::Terminal(a), Letter::NonTerminal(2)] },
+            Production { lhs: 0, rhs: vec![Letter::Terminal(b), Letter::NonTerminal(1)] },
+            Production { lhs: 1, rhs: vec![Letter::Terminal(a), Letter::NonTerminal(1)] },
+            Production { lhs: 1, rhs: vec![Letter::Terminal(b), Letter::NonTerminal(2)] },
+            Production { lhs: 2, rhs: vec![Letter::Terminal(a), Letter::NonTerminal(2)] },
+            Production { lhs: 2, rhs: vec![Letter::Terminal(b), Letter::NonTerminal(2)] },
+            Production { lhs: 1, rhs: vec![Letter::Terminal(EPSILON)] },
+        ];
 
-        assert_eq!(grammar, result);
+        assert_eq!(grammar.get_start_symbol(), 0);
+        assert_eq!(grammar.get_productions(), &productions);
+        assert_eq!(grammar.get_symbols().get_non_terminal_name(2), "X2");
     }
 
     #[test]
     fn test_remove_useless() {
-        let mut grammar = {
-            // S -> AB | a
-            // B -> b
-
-            // S = 0
-            // B = 1
-            // A = 2
-
-            Grammar {
-                start_symbol: 0,
-                productions: vec![
-                    Production { lhs: 0, rhs: vec![Letter::NonTerminal(1), Letter::NonTerminal(2)] },
-                    Production { lhs: 0, rhs: vec![Letter::Terminal('a')] },
-                    Production { lhs: 1, rhs: vec![Letter::Terminal('b')] },
-                ],
-                nullable: None,
-            }
-        };
+        let mut symbols = SymbolTable::new();
+        // S -> B A | a
+        // B -> b
+        // A has no productions
+        let s = symbols.add_non_terminal("S");
+        let b = symbols.add_non_terminal("B");
+        let a = symbols.add_non_terminal("A");
+        let terminal_a = symbols.add_terminal("a");
+        let terminal_b = symbols.add_terminal("b");
+
+        let mut grammar = Grammar::new(
+            s,
+            vec![
+                Production { lhs: s, rhs: vec![Letter::NonTerminal(b), Letter::NonTerminal(a)] },
+                Production { lhs: s, rhs: vec![Letter::Terminal(terminal_a)] },
+                Production { lhs: b, rhs: vec![Letter::Terminal(terminal_b)] },
+            ],
+            symbols.clone(),
+        );
 
         grammar.remove_useless();
 
-        let result = Grammar {
-            start_symbol: 0,
-            productions: vec![
-                Production { lhs: 0, rhs: vec![Letter::Terminal('a')] },
+        let result = Grammar::new(
+            s,
+            vec![
+                Production { lhs: s, rhs: vec![Letter::Terminal(terminal_a)] },
             ],
-            nullable: None,
-        };
+            symbols,
+        );
 
         assert_eq!(grammar, result);
     }
 
     #[test]
     fn test_remove_unitary_cycles() {
-        // E -> E + T | T
-        // T -> T * F | F
-        // F -> (E) | a 
-        
-        let mut grammar = Grammar {
-            start_symbol: 0,
-            productions: vec![
-                Production { lhs: 0, rhs: vec![Letter::NonTerminal(0), Letter::Terminal('+'), Letter::NonTerminal(1)] },
-                Production { lhs: 0, rhs: vec![Letter::NonTerminal(1)] },
-                Production { lhs: 1, rhs: vec![Letter::NonTerminal(1), Letter::Terminal('*'), Letter::NonTerminal(2)] },
-                Production { lhs: 1, rhs: vec![Letter::NonTerminal(2)] },
-                Production { lhs: 2, rhs: vec![Letter::Terminal('('), Letter::NonTerminal(0), Letter::Terminal(')')] },
-                Production { lhs: 2, rhs: vec![Letter::Terminal('a')] },
-            ],
-            nullable: None,
-        };
-
-        let result = Grammar {
-            // E -> E + T | T * F | (E) | a
-            // T -> T * F | (E) | a
-            // F -> (E) | a
-            start_symbol: 0,
-            productions: vec![
-                Production { lhs: 0, rhs: vec![Letter::NonTerminal(0), Letter::Terminal('+'), Letter::NonTerminal(1)] },
-                Production { lhs: 0, rhs: vec![Letter::NonTerminal(1), Letter::Terminal('*'), Letter::NonTerminal(2)] },
-                Production { lhs: 0, rhs: vec![Letter::Terminal('('), Letter::NonTerminal(0), Letter::Terminal(')')] },
-                Production { lhs: 0, rhs: vec![Letter::Terminal('a')] },
-                Production { lhs: 1, rhs: vec![Letter::NonTerminal(1), Letter::Terminal('*'), Letter::NonTerminal(2)] },
-                Production { lhs: 1, rhs: vec![Letter::Terminal('('), Letter::NonTerminal(0), Letter::Terminal(')')] },
-                Production { lhs: 1, rhs: vec![Letter::Terminal('a')] },
-                Production { lhs: 2, rhs: vec![Letter::Terminal('('), Letter::NonTerminal(0), Letter::Terminal(')')] },
-                Production { lhs: 2, rhs: vec![Letter::Terminal('a')] },
-            ],
-            nullable: None,
-        };
+        let mut grammar = Grammar::from_text("
+            E -> E + T | T
+            T -> T * F | F
+            F -> ( E ) | a
+        ").unwrap();
+
+        let result = Grammar::from_text("
+            E -> E + T | T * F | ( E ) | a
+            T -> T * F | ( E ) | a
+            F -> ( E ) | a
+        ").unwrap();
 
         grammar.remove_unitary_cycles();
 
@@ -632,21 +805,275 @@ mod test {
 
         let items = grammar.get_itemization();
 
-        let result_productions = vec![
-            Production { lhs: 0, rhs: vec![Letter::Terminal(ITEM_SEP), Letter::NonTerminal(1), Letter::Terminal('b')] },
-            Production { lhs: 0, rhs: vec![Letter::NonTerminal(1), Letter::Terminal(ITEM_SEP), Letter::Terminal('b')] },
-            Production { lhs: 0, rhs: vec![Letter::NonTerminal(1), Letter::Terminal('b'), Letter::Terminal(ITEM_SEP)] },
-
-            Production { lhs: 0, rhs: vec![Letter::Terminal(ITEM_SEP), Letter::Terminal('c')] },
-            Production { lhs: 0, rhs: vec![Letter::Terminal('c'), Letter::Terminal(ITEM_SEP)] },
-            
-            Production { lhs: 1, rhs: vec![Letter::Terminal(ITEM_SEP), Letter::Terminal('a'), Letter::NonTerminal(1)] },
-            Production { lhs: 1, rhs: vec![Letter::Terminal('a'), Letter::Terminal(ITEM_SEP), Letter::NonTerminal(1)] },
-            Production { lhs: 1, rhs: vec![Letter::Terminal('a'), Letter::NonTerminal(1), Letter::Terminal(ITEM_SEP)] },
-            Production { lhs: 1, rhs: vec![Letter::Terminal(ITEM_SEP)] },
+        // S -> A b, S -> c, A -> a A, A -> ε
+        let result_items = vec![
+            Item { production: 0, dot: 0 },
+            Item { production: 0, dot: 1 },
+            Item { production: 0, dot: 2 },
+
+            Item { production: 1, dot: 0 },
+            Item { production: 1, dot: 1 },
+
+            Item { production: 2, dot: 0 },
+            Item { production: 2, dot: 1 },
+            Item { production: 2, dot: 2 },
+
+            Item { production: 3, dot: 0 },
         ];
 
-        assert!(items.iter().all(|item| result_productions.contains(item)));
-        assert!(result_productions.iter().all(|item| items.contains(item)));
+        assert!(items.iter().all(|item| result_items.contains(item)));
+        assert!(result_items.iter().all(|item| items.contains(item)));
+
+        assert_eq!(grammar.item_to_string(&Item { production: 0, dot: 1 }), "S -> A . b");
+        assert_eq!(grammar.item_to_string(&Item { production: 3, dot: 0 }), "A -> .");
+    }
+
+    #[test]
+    fn test_named_symbols() {
+        // multi character terminals, and a dot as terminal
+        let mut grammar = Grammar::from_text("
+            S -> id := E '.' | begin S end
+            E -> num | id | ε
+        ").unwrap();
+
+        let first = grammar.first(&Letter::NonTerminal(non_terminal(&grammar, "S")));
+        let first: BTreeSet<&str> = first
+            .into_iter()
+            .map(|terminal| grammar.get_symbols().get_terminal_name(terminal))
+            .collect();
+        assert_eq!(first, BTreeSet::from(["id", "begin"]));
+
+        let follow = grammar.follow(&non_terminal(&grammar, "E"));
+        let follow: BTreeSet<&str> = follow
+            .into_iter()
+            .map(|terminal| grammar.get_symbols().get_terminal_name(terminal))
+            .collect();
+        assert_eq!(follow, BTreeSet::from(["."]));
+
+        assert_eq!(grammar.to_string(), "S -> id := E . | begin S end\nE -> num | id | ε");
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let text = "S -> A 'A' '|' | \"'\" x\nA -> 'ε' | ε | '->' A";
+        let grammar = Grammar::from_text(text).unwrap();
+
+        assert_eq!(grammar.to_string(), text);
+        assert_eq!(Grammar::from_text(&grammar.to_string()).unwrap(), grammar);
+    }
+
+    #[test]
+    fn test_dollar_terminal() {
+        // a terminal named $ is not the end of the string
+        let text = "S -> '$' S | a";
+        let grammar = Grammar::from_text(text).unwrap();
+        let dollar = terminal(&grammar, "$");
+
+        assert_ne!(dollar, STRING_END);
+        assert_eq!(grammar.terminal_to_string(dollar), "'$'");
+        assert_eq!(grammar.terminal_to_string(STRING_END), "$");
+        assert_eq!(grammar.to_string(), text);
+        assert_eq!(Grammar::from_text(&grammar.to_string()).unwrap(), grammar);
+    }
+
+    /// returns the names of the terminals in the sets of each non terminal
+    fn name_table(grammar: &Grammar, table: BTreeMap<NonTerminal, BTreeSet<Terminal>>) -> BTreeMap<String, BTreeSet<String>> {
+        let symbols = grammar.get_symbols();
+        table
+            .into_iter()
+            .map(|(non_terminal, terminals)| {
+                (
+                    symbols.get_non_terminal_name(non_terminal).to_string(),
+                    terminals.into_iter().map(|terminal| symbols.get_terminal_name(terminal).to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn first_names(grammar: &mut Grammar) -> BTreeMap<String, BTreeSet<String>> {
+        let table = grammar.get_first_table().clone();
+        name_table(grammar, table)
+    }
+
+    fn follow_names(grammar: &mut Grammar) -> BTreeMap<String, BTreeSet<String>> {
+        let table = grammar.get_follow_table().clone();
+        name_table(grammar, table)
+    }
+
+    fn set(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_expression_grammar_sets() {
+        // left recursive, FIRST must not recurse forever
+        let mut grammar = expression_grammar();
+
+        let first = first_names(&mut grammar);
+        let follow = follow_names(&mut grammar);
+
+        for non_terminal in ["E", "T", "F"] {
+            assert_eq!(first[non_terminal], set(&["(", "id"]));
+        }
+        assert_eq!(follow["E"], set(&["$", "+", ")"]));
+        assert_eq!(follow["T"], set(&["$", "+", "*", ")"]));
+        assert_eq!(follow["F"], set(&["$", "+", "*", ")"]));
+    }
+
+    #[test]
+    fn test_ll_expression_grammar_sets() {
+        let mut grammar = ll_expression_grammar();
+
+        let first = first_names(&mut grammar);
+        let follow = follow_names(&mut grammar);
+
+        assert_eq!(first["E"], set(&["(", "id"]));
+        assert_eq!(first["E'"], set(&["+", "ε"]));
+        assert_eq!(first["T'"], set(&["*", "ε"]));
+        assert_eq!(follow["E"], set(&["$", ")"]));
+        assert_eq!(follow["E'"], set(&["$", ")"]));
+        assert_eq!(follow["T"], set(&["$", "+", ")"]));
+        assert_eq!(follow["T'"], set(&["$", "+", ")"]));
+        assert_eq!(follow["F"], set(&["$", "+", "*", ")"]));
+    }
+
+    #[test]
+    fn test_first_of_sequence() {
+        let mut grammar = get_test_grammar();
+        let (s, a) = (non_terminal(&grammar, "S"), non_terminal(&grammar, "A"));
+        let (terminal_a, terminal_c) = (terminal(&grammar, "a"), terminal(&grammar, "c"));
+
+        let first = grammar.first_of_sequence(&[Letter::NonTerminal(a), Letter::NonTerminal(a)]);
+        assert_eq!(first, BTreeSet::from([EPSILON, terminal_a]));
+
+        let first = grammar.first_of_sequence(&[Letter::NonTerminal(a), Letter::Terminal(terminal_c), Letter::NonTerminal(s)]);
+        assert_eq!(first, BTreeSet::from([terminal_a, terminal_c]));
+
+        assert_eq!(grammar.first_of_sequence(&[]), BTreeSet::from([EPSILON]));
+    }
+
+    #[test]
+    fn test_mutually_recursive_follow() {
+        // FOLLOW(A) and FOLLOW(B) depend on each other
+        let mut grammar = Grammar::from_text("
+            S -> A c | B d
+            A -> a B | x
+            B -> b A | y | ε
+        ").unwrap();
+
+        let follow = follow_names(&mut grammar);
+
+        assert_eq!(follow["S"], set(&["$"]));
+        assert_eq!(follow["A"], set(&["c", "d"]));
+        assert_eq!(follow["B"], set(&["c", "d"]));
+
+        // the tables are computed again after a transformation
+        grammar.remove_unitary_cycles();
+        assert_eq!(follow_names(&mut grammar)["A"], set(&["c", "d"]));
+    }
+
+    fn production_strings(grammar: &Grammar) -> BTreeSet<String> {
+        (0..grammar.get_productions().len())
+            .map(|production| grammar.production_to_string(production))
+            .collect()
+    }
+
+    #[test]
+    fn test_remove_unitary_chain() {
+        // A has only the unitary production A -> B
+        let mut grammar = Grammar::from_text("S -> A | a\nA -> B\nB -> b").unwrap();
+
+        grammar.remove_unitary_cycles();
+
+        let expected: BTreeSet<String> = ["S -> a", "S -> b", "A -> b", "B -> b"]
+            .iter()
+            .map(|production| production.to_string())
+            .collect();
+        assert_eq!(production_strings(&grammar), expected);
+        assert_eq!(grammar.get_productions().len(), expected.len());
+    }
+
+    #[test]
+    fn test_remove_epsilon_chain() {
+        // A is nullable through B and C
+        let mut grammar = Grammar::from_text("
+            S -> A B C d
+            A -> B C
+            B -> b | ε
+            C -> c | ε
+        ").unwrap();
+
+        grammar.remove_epsilon();
+
+        let expected = [
+            "S -> A B C d", "S -> B C d", "S -> A C d", "S -> C d",
+            "S -> A B d", "S -> B d", "S -> A d", "S -> d",
+            "A -> B C", "A -> C", "A -> B",
+            "B -> b", "C -> c",
+        ];
+        let expected: BTreeSet<String> = expected.iter().map(|production| production.to_string()).collect();
+        assert_eq!(production_strings(&grammar), expected);
+        assert_eq!(grammar.get_productions().len(), expected.len());
+        assert!(grammar.get_nullable().is_empty());
+        assert_eq!(grammar.get_start_symbol(), non_terminal(&grammar, "S"));
+    }
+
+    #[test]
+    fn test_remove_epsilon_nullable_start() {
+        let mut grammar = Grammar::from_text("
+            S -> A B
+            A -> a A | ε
+            B -> b | A
+        ").unwrap();
+        let a = non_terminal(&grammar, "A");
+        assert!(grammar.first(&Letter::NonTerminal(a)).contains(&EPSILON));
+
+        grammar.remove_epsilon();
+
+        // the cached FIRST sets are computed again
+        assert!(!grammar.first(&Letter::NonTerminal(a)).contains(&EPSILON));
+        assert_eq!(grammar.get_symbols().get_non_terminal_name(grammar.get_start_symbol()), "S'");
+        assert_eq!(grammar.get_nullable(), BTreeSet::from([grammar.get_start_symbol()]));
+        assert_eq!(
+            grammar.to_string(),
+            "S' -> S | ε\nS -> A B | B | A\nA -> a A | a\nB -> b | A"
+        );
+    }
+
+    #[test]
+    fn test_remove_epsilon_drops_empty_non_terminals() {
+        let mut grammar = Grammar::from_text("S -> A b\nA -> ε").unwrap();
+        grammar.remove_epsilon();
+        assert_eq!(grammar.to_string(), "S -> b");
+
+        // B only derives ε through A
+        let mut grammar = Grammar::from_text("S -> a B\nB -> A\nA -> ε").unwrap();
+        grammar.remove_epsilon();
+        assert_eq!(grammar.to_string(), "S -> a");
+
+        let mut grammar = Grammar::from_text("S -> ε").unwrap();
+        grammar.remove_epsilon();
+        assert_eq!(grammar.to_string(), "S' -> ε");
+    }
+
+    #[test]
+    fn test_remove_epsilon_skips_self_loops() {
+        let mut grammar = Grammar::from_text("S -> S B | a\nB -> b | ε").unwrap();
+
+        grammar.remove_epsilon();
+
+        assert_eq!(grammar.to_string(), "S -> S B | a\nB -> b");
+    }
+
+    #[test]
+    fn test_remove_epsilon_many_nullable_letters() {
+        let rhs = vec!["A"; 70].join(" ");
+        let mut grammar = Grammar::from_text(&format!("S -> b {}\nA -> a | ε", rhs)).unwrap();
+
+        grammar.remove_epsilon();
+
+        let s = non_terminal(&grammar, "S");
+        let s_productions = grammar.get_productions().iter().filter(|production| production.lhs == s).count();
+        assert_eq!(s_productions, 71);
+    }
+}
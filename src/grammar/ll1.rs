@@ -0,0 +1,287 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+use crate::error::ParserConflict;
+use crate::grammar::{Grammar, Letter, NonTerminal, ParseResult, ParseStep, Terminal, EPSILON, STRING_END};
+use crate::utils::{Graph, IndNode};
+
+/// LL(1) predictive parsing table: the cell (A, a) has the productions to
+/// expand A with when A is on top of the stack and a is the next terminal.
+///
+/// The grammar is LL(1) if no cell has more than one production.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LL1Table {
+    non_terminals: Vec<NonTerminal>,
+    // the terminals of the grammar and $
+    terminals: Vec<Terminal>,
+    cells: BTreeMap<(NonTerminal, Terminal), Vec<usize>>,
+    // synchronizing terminals of the panic mode recovery
+    follow: BTreeMap<NonTerminal, BTreeSet<Terminal>>,
+}
+
+impl LL1Table {
+    pub fn get_non_terminals(&self) -> &Vec<NonTerminal> {
+        &self.non_terminals
+    }
+
+    pub fn get_terminals(&self) -> &Vec<Terminal> {
+        &self.terminals
+    }
+
+    /// returns the indexes of the productions in the cell, empty for an error cell
+    pub fn get_productions(&self, non_terminal: NonTerminal, terminal: Terminal) -> &[usize] {
+        self.cells
+            .get(&(non_terminal, terminal))
+            .map(|productions| productions.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// returns the cells with more than one production, with the competing productions
+    pub fn get_conflicts(&self) -> Vec<(NonTerminal, Terminal, Vec<usize>)> {
+        self.cells
+            .iter()
+            .filter(|(_, productions)| productions.len() > 1)
+            .map(|((non_terminal, terminal), productions)| (*non_terminal, *terminal, productions.clone()))
+            .collect()
+    }
+
+    pub fn is_ll1(&self) -> bool {
+        self.cells.values().all(|productions| productions.len() <= 1)
+    }
+
+    /// runs the predictive parser on the input, the grammar must be the one of the table.
+    ///
+    /// On a syntax error it recovers in panic mode: a terminal on top of the stack
+    /// that doesn't match is popped, as if it was inserted in the input. With a non
+    /// terminal A and an empty cell, A is popped if the next terminal is in FOLLOW(A)
+    /// (or it's the end of the input), otherwise the next terminal is skipped.
+    ///
+    /// returns an error if the table has conflicts
+    pub fn parse(&self, grammar: &Grammar, input: &[Terminal]) -> Result<ParseResult, Box<dyn Error>> {
+        if !self.is_ll1() {
+            return Err(Box::new(ParserConflict::new("LL(1)".to_string())));
+        }
+
+        let mut tree = Graph::new();
+        let root = tree.add_node(Some(grammar.letter_to_string(&Letter::NonTerminal(grammar.get_start_symbol()))));
+        tree.start_node = Some(root);
+
+        // the bottom of the stack is $, it has no node in the tree
+        let mut stack: Vec<(Letter, Option<IndNode>)> = vec![
+            (Letter::Terminal(STRING_END), None),
+            (Letter::NonTerminal(grammar.get_start_symbol()), Some(root)),
+        ];
+        let mut position = 0;
+        let mut steps = vec![];
+        let mut errors = vec![];
+
+        loop {
+            let next = input.get(position).cloned().unwrap_or(STRING_END);
+            let mut step = ParseStep {
+                stack: stack
+                    .iter()
+                    .map(|(letter, _)| grammar.letter_to_string(letter))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+                symbols: None,
+                input: grammar.input_to_string(&input[position.min(input.len())..]),
+                action: String::new(),
+            };
+            let (top, node) = stack.last().cloned().unwrap();
+
+            match top {
+                Letter::Terminal(STRING_END) if next == STRING_END => {
+                    step.action = "accept".to_string();
+                    steps.push(step);
+                    break;
+                }
+                Letter::Terminal(STRING_END) => {
+                    let error = format!("unexpected {} after the end of the derivation", grammar.terminal_to_string(next));
+                    step.action = format!("error: {}, skip it", error);
+                    errors.push(error);
+                    position += 1;
+                }
+                Letter::Terminal(terminal) if terminal == next => {
+                    step.action = format!("match {}", grammar.terminal_to_string(terminal));
+                    stack.pop();
+                    position += 1;
+                }
+                Letter::Terminal(terminal) => {
+                    let error = format!(
+                        "expected {}, found {}",
+                        grammar.terminal_to_string(terminal),
+                        grammar.terminal_to_string(next)
+                    );
+                    step.action = format!("error: {}, pop {}", error, grammar.terminal_to_string(terminal));
+                    errors.push(error);
+                    stack.pop();
+                }
+                Letter::NonTerminal(non_terminal) => match self.get_productions(non_terminal, next).first() {
+                    Some(production) => {
+                        step.action = grammar.production_to_string(*production);
+                        stack.pop();
+
+                        let rhs = grammar.get_productions()[*production].get_rhs();
+                        let mut children = vec![];
+                        for letter in rhs.iter() {
+                            let child = tree.add_node(Some(grammar.letter_to_string(letter)));
+                            tree.add_edge(node.unwrap(), child, None);
+                            children.push((letter.clone(), Some(child)));
+                        }
+                        // ε has a leaf in the tree, but nothing to match
+                        children.retain(|(letter, _)| *letter != Letter::Terminal(EPSILON));
+                        stack.extend(children.into_iter().rev());
+                    }
+                    None => {
+                        let name = grammar.letter_to_string(&top);
+                        let error = format!("no production of {} starts with {}", name, grammar.terminal_to_string(next));
+                        if next == STRING_END || self.follow[&non_terminal].contains(&next) {
+                            step.action = format!("error: {}, pop {}", error, name);
+                            stack.pop();
+                        } else {
+                            step.action = format!("error: {}, skip {}", error, grammar.terminal_to_string(next));
+                            position += 1;
+                        }
+                        errors.push(error);
+                    }
+                },
+            }
+            steps.push(step);
+        }
+
+        Ok(ParseResult { steps, tree, errors })
+    }
+}
+
+impl Grammar {
+    /// builds the LL(1) table: the production A -> α is in the cells (A, a) for every
+    /// a in FIRST(α), and if α is nullable in the cells (A, b) for every b in FOLLOW(A)
+    pub fn get_ll1_table(&mut self) -> LL1Table {
+        let mut cells: BTreeMap<(NonTerminal, Terminal), Vec<usize>> = BTreeMap::new();
+        for index in 0..self.get_productions().len() {
+            let production = self.get_productions()[index].clone();
+            let mut lookaheads = self.first_of_sequence(production.get_rhs());
+            if lookaheads.remove(&EPSILON) {
+                lookaheads.extend(self.follow(&production.get_lhs()));
+            }
+
+            for terminal in lookaheads {
+                let cell = cells.entry((production.get_lhs(), terminal)).or_default();
+                if !cell.contains(&index) {
+                    cell.push(index);
+                }
+            }
+        }
+
+        let mut terminals = self.get_symbols().get_terminals();
+        terminals.push(STRING_END);
+        LL1Table {
+            non_terminals: (0..self.get_symbols().get_num_non_terminals()).collect(),
+            terminals,
+            cells,
+            follow: self.get_follow_table().clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grammar::test_utils::{ll_expression_grammar, non_terminal, terminal};
+
+    #[test]
+    fn expression_grammar_table() {
+        let mut grammar = ll_expression_grammar();
+        let table = grammar.get_ll1_table();
+        let cell = |non_terminal, terminal| {
+            let productions = table.get_productions(non_terminal, terminal);
+            productions.iter().map(|production| grammar.production_to_string(*production)).collect::<Vec<String>>()
+        };
+
+        let (e, e1, t1) = (non_terminal(&grammar, "E"), non_terminal(&grammar, "E'"), non_terminal(&grammar, "T'"));
+        let (plus, times, open, close, id) = (
+            terminal(&grammar, "+"),
+            terminal(&grammar, "*"),
+            terminal(&grammar, "("),
+            terminal(&grammar, ")"),
+            terminal(&grammar, "id"),
+        );
+
+        assert!(table.is_ll1());
+        assert_eq!(cell(e, id), vec!["E -> T E'"]);
+        assert_eq!(cell(e, open), vec!["E -> T E'"]);
+        assert_eq!(cell(e, plus), Vec::<String>::new());
+        assert_eq!(cell(e1, plus), vec!["E' -> + T E'"]);
+        assert_eq!(cell(e1, close), vec!["E' -> ε"]);
+        assert_eq!(cell(e1, STRING_END), vec!["E' -> ε"]);
+        assert_eq!(cell(t1, plus), vec!["T' -> ε"]);
+        assert_eq!(cell(t1, times), vec!["T' -> * F T'"]);
+        assert_eq!(table.get_terminals().len(), 6);
+    }
+
+    #[test]
+    fn parse_expression() {
+        let mut grammar = ll_expression_grammar();
+        let table = grammar.get_ll1_table();
+        let input = grammar.tokenize("id + id * id").unwrap();
+        let result = table.parse(&grammar, &input).unwrap();
+
+        assert!(result.errors.is_empty());
+        let first = &result.steps[0];
+        assert_eq!((first.stack.as_str(), first.input.as_str()), ("$ E", "id + id * id $"));
+        assert_eq!(first.action, "E -> T E'");
+        assert_eq!(result.steps[3].action, "match id");
+        assert_eq!(result.steps.last().unwrap().action, "accept");
+        let leaves = result.tree.get_nodes().iter().filter(|node| result.tree.get_node_edges(node.id).is_empty());
+        let leaves: Vec<String> = leaves.filter_map(|node| node.label.clone()).collect();
+        assert_eq!(leaves.iter().filter(|label| *label == "id").count(), 3);
+        assert_eq!(leaves.iter().filter(|label| *label == "ε").count(), 3);
+        assert_eq!(result.tree.start_node, Some(0));
+    }
+
+    #[test]
+    fn panic_mode_recovery() {
+        let mut grammar = ll_expression_grammar();
+        let table = grammar.get_ll1_table();
+
+        // the second id is not in FOLLOW(T'), so it's skipped
+        let result = table.parse(&grammar, &grammar.tokenize("id id * + id").unwrap()).unwrap();
+        assert!(!result.errors.is_empty());
+        assert!(result.steps.iter().any(|step| step.action == "error: no production of T' starts with id, skip id"));
+        // + is in FOLLOW(F), so F is popped
+        assert!(result.steps.iter().any(|step| step.action == "error: no production of F starts with +, pop F"));
+        assert_eq!(result.errors.len(), 2);
+        assert_eq!(result.steps.last().unwrap().action, "accept");
+
+        // the missing ) is popped from the stack
+        let result = table.parse(&grammar, &grammar.tokenize("( id").unwrap()).unwrap();
+        assert_eq!(result.errors, vec!["expected ), found $"]);
+
+        assert!(grammar.tokenize("id - id").is_err());
+    }
+
+    #[test]
+    fn left_recursion_conflicts() {
+        let mut grammar = Grammar::from_text("E -> E + id | id").unwrap();
+        let table = grammar.get_ll1_table();
+
+        assert!(!table.is_ll1());
+        let (e, id) = (non_terminal(&grammar, "E"), terminal(&grammar, "id"));
+        assert_eq!(table.get_conflicts(), vec![(e, id, vec![0, 1])]);
+        assert!(table.parse(&grammar, &[id]).is_err());
+    }
+
+    #[test]
+    fn dangling_else_conflict() {
+        // the conflict is between the else branch and the ε production in FOLLOW
+        let mut grammar = Grammar::from_text("
+            S -> if c then S E | a
+            E -> else S | ε
+        ").unwrap();
+        let table = grammar.get_ll1_table();
+
+        let (e, else_) = (non_terminal(&grammar, "E"), terminal(&grammar, "else"));
+        assert_eq!(table.get_conflicts(), vec![(e, else_, vec![2, 3])]);
+        assert_eq!(table.get_productions(e, STRING_END), &[3]);
+    }
+}
@@ -0,0 +1,584 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt;
+
+use crate::error::ParserConflict;
+use crate::grammar::lr0::canonical_collection;
+use crate::grammar::{Grammar, Item, Letter, NonTerminal, ParseResult, ParseStep, Terminal, EPSILON, STRING_END};
+use crate::utils::{Graph, IndNode};
+
+/// LR(1) items, each LR(0) item with its lookaheads
+pub type LR1ItemSet = BTreeMap<Item, BTreeSet<Terminal>>;
+
+/// kinds of LR parsing tables, they differ in the lookaheads of the reductions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LRKind {
+    /// LR(0) states, reduce on FOLLOW of the non terminal
+    SLR1,
+    /// LR(1) states with the same items (the core) merged together
+    LALR1,
+    /// canonical LR(1) states
+    LR1,
+}
+
+impl LRKind {
+    pub const ALL: [LRKind; 3] = [LRKind::SLR1, LRKind::LALR1, LRKind::LR1];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LRKind::SLR1 => "SLR(1)",
+            LRKind::LALR1 => "LALR(1)",
+            LRKind::LR1 => "LR(1)",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LRAction {
+    /// push the state
+    Shift(usize),
+    /// reduce with the production with this index
+    Reduce(usize),
+    Accept,
+}
+
+impl fmt::Display for LRAction {
+    /// shows the action like in the textbooks: s3, r2 and acc
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LRAction::Shift(state) => write!(f, "s{}", state),
+            LRAction::Reduce(production) => write!(f, "r{}", production),
+            LRAction::Accept => write!(f, "acc"),
+        }
+    }
+}
+
+/// a cell of the ACTION table with more than one action,
+/// with the items of the state that cause each action
+#[derive(Debug, Clone, PartialEq)]
+pub struct LRConflict {
+    pub state: usize,
+    pub terminal: Terminal,
+    pub actions: Vec<LRAction>,
+    pub items: Vec<String>,
+}
+
+impl LRConflict {
+    pub fn is_shift_reduce(&self) -> bool {
+        self.actions.iter().any(|action| matches!(action, LRAction::Shift(_)))
+    }
+}
+
+/// ACTION and GOTO tables of an LR parser of the augmented grammar
+#[derive(Debug, Clone)]
+pub struct LRTable {
+    kind: LRKind,
+    grammar: Grammar,
+    states: Vec<LR1ItemSet>,
+    actions: BTreeMap<(usize, Terminal), Vec<LRAction>>,
+    gotos: BTreeMap<(usize, NonTerminal), usize>,
+}
+
+impl LRTable {
+    /// builds the tables from the states and their transitions: an item A -> α . a β
+    /// shifts a, a complete item A -> α . reduces on its lookaheads, S' -> S . accepts on $
+    fn new(
+        kind: LRKind,
+        grammar: Grammar,
+        states: Vec<LR1ItemSet>,
+        transitions: Vec<BTreeMap<Letter, usize>>,
+    ) -> Self {
+        let start_production = grammar.get_productions().len() - 1;
+        let mut actions: BTreeMap<(usize, Terminal), Vec<LRAction>> = BTreeMap::new();
+        let mut gotos = BTreeMap::new();
+        let mut add_action = |state, terminal, action| {
+            let cell = actions.entry((state, terminal)).or_default();
+            if !cell.contains(&action) {
+                cell.push(action);
+                cell.sort();
+            }
+        };
+
+        for (state, items) in states.iter().enumerate() {
+            for (item, lookaheads) in items.iter() {
+                match grammar.get_next_letter(item) {
+                    Some(letter @ Letter::Terminal(terminal)) => {
+                        add_action(state, *terminal, LRAction::Shift(transitions[state][letter]))
+                    }
+                    Some(Letter::NonTerminal(_)) => {}
+                    None if item.production == start_production => add_action(state, STRING_END, LRAction::Accept),
+                    None => {
+                        for lookahead in lookaheads.iter() {
+                            add_action(state, *lookahead, LRAction::Reduce(item.production));
+                        }
+                    }
+                }
+            }
+            for (letter, to) in transitions[state].iter() {
+                if let Letter::NonTerminal(non_terminal) = letter {
+                    gotos.insert((state, *non_terminal), *to);
+                }
+            }
+        }
+
+        Self {
+            kind,
+            grammar,
+            states,
+            actions,
+            gotos,
+        }
+    }
+
+    pub fn get_kind(&self) -> LRKind {
+        self.kind
+    }
+
+    /// returns the augmented grammar, the reductions refer to its productions
+    pub fn get_grammar(&self) -> &Grammar {
+        &self.grammar
+    }
+
+    pub fn get_num_states(&self) -> usize {
+        self.states.len()
+    }
+
+    /// returns the terminals of the ACTION table, $ included
+    pub fn get_terminals(&self) -> Vec<Terminal> {
+        let mut terminals = self.grammar.get_symbols().get_terminals();
+        terminals.push(STRING_END);
+        terminals
+    }
+
+    /// returns the non terminals of the GOTO table, the augmented start symbol excluded
+    pub fn get_non_terminals(&self) -> Vec<NonTerminal> {
+        (0..self.grammar.get_symbols().get_num_non_terminals())
+            .filter(|non_terminal| *non_terminal != self.grammar.get_start_symbol())
+            .collect()
+    }
+
+    /// returns the actions of the cell, empty for an error cell
+    pub fn get_actions(&self, state: usize, terminal: Terminal) -> &[LRAction] {
+        self.actions
+            .get(&(state, terminal))
+            .map(|actions| actions.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn get_goto(&self, state: usize, non_terminal: NonTerminal) -> Option<usize> {
+        self.gotos.get(&(state, non_terminal)).cloned()
+    }
+
+    /// shows the item, with its lookaheads for the LR(1) and LALR(1) tables
+    fn item_to_string(&self, item: &Item, lookaheads: &BTreeSet<Terminal>) -> String {
+        let item_string = self.grammar.item_to_string(item);
+        if self.kind == LRKind::SLR1 {
+            return item_string;
+        }
+        let lookaheads: Vec<String> = lookaheads
+            .iter()
+            .map(|terminal| self.grammar.terminal_to_string(*terminal))
+            .collect();
+        format!("[{}, {}]", item_string, lookaheads.join("/"))
+    }
+
+    /// shows the items of the state one for each line
+    pub fn state_to_string(&self, state: usize) -> String {
+        self.states[state]
+            .iter()
+            .map(|(item, lookaheads)| self.item_to_string(item, lookaheads))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn get_conflicts(&self) -> Vec<LRConflict> {
+        let mut conflicts = vec![];
+        for ((state, terminal), actions) in self.actions.iter() {
+            if actions.len() < 2 {
+                continue;
+            }
+
+            // the items that shift the terminal, and the complete items of the reductions
+            // and of the accept action, S' -> S .
+            let start_production = self.grammar.get_productions().len() - 1;
+            let items = self.states[*state]
+                .iter()
+                .filter(|(item, _)| match self.grammar.get_next_letter(item) {
+                    Some(letter) => *letter == Letter::Terminal(*terminal),
+                    None if item.production == start_production => actions.contains(&LRAction::Accept),
+                    None => actions.contains(&LRAction::Reduce(item.production)),
+                })
+                .map(|(item, lookaheads)| self.item_to_string(item, lookaheads))
+                .collect();
+            conflicts.push(LRConflict {
+                state: *state,
+                terminal: *terminal,
+                actions: actions.clone(),
+                items,
+            });
+        }
+
+        conflicts
+    }
+
+    pub fn has_conflicts(&self) -> bool {
+        self.actions.values().any(|actions| actions.len() > 1)
+    }
+
+    /// runs the shift-reduce parser on the input: a shift pushes the next terminal
+    /// and the state, a reduction by A -> α pops |α| states and symbols and pushes A
+    /// and the state GOTO(top, A). The parse tree is built from the leaves, every
+    /// reduction adds the node of A above the nodes of α.
+    ///
+    /// The parser stops at the first syntax error, the tree is then incomplete.
+    /// returns an error if the table has conflicts
+    pub fn parse(&self, input: &[Terminal]) -> Result<ParseResult, Box<dyn Error>> {
+        if self.has_conflicts() {
+            return Err(Box::new(ParserConflict::new(self.kind.name().to_string())));
+        }
+
+        let grammar = &self.grammar;
+        let mut tree = Graph::new();
+        let mut states = vec![0];
+        let mut symbols: Vec<(Letter, IndNode)> = vec![];
+        let mut position = 0;
+        let mut steps = vec![];
+        let mut errors = vec![];
+
+        loop {
+            let next = input.get(position).cloned().unwrap_or(STRING_END);
+            let state = *states.last().unwrap();
+            // the symbol stack starts with $, like the stack of the LL parser
+            let symbol_names: Vec<String> = std::iter::once("$".to_string())
+                .chain(symbols.iter().map(|(letter, _)| grammar.letter_to_string(letter)))
+                .collect();
+            let mut step = ParseStep {
+                stack: states.iter().map(|state| state.to_string()).collect::<Vec<String>>().join(" "),
+                symbols: Some(symbol_names.join(" ")),
+                input: grammar.input_to_string(&input[position.min(input.len())..]),
+                action: String::new(),
+            };
+
+            match self.get_actions(state, next).first() {
+                Some(LRAction::Shift(to)) => {
+                    step.action = format!("shift {}, go to {}", grammar.terminal_to_string(next), to);
+                    let node = tree.add_node(Some(grammar.terminal_to_string(next)));
+                    symbols.push((Letter::Terminal(next), node));
+                    states.push(*to);
+                    position += 1;
+                }
+                Some(LRAction::Reduce(index)) => {
+                    let production = &grammar.get_productions()[*index];
+                    let len = if production.is_epsilon() { 0 } else { production.get_rhs().len() };
+                    let children = symbols.split_off(symbols.len() - len);
+                    states.truncate(states.len() - len);
+
+                    let lhs = Letter::NonTerminal(production.get_lhs());
+                    let node = tree.add_node(Some(grammar.letter_to_string(&lhs)));
+                    if production.is_epsilon() {
+                        let leaf = tree.add_node(Some(grammar.terminal_to_string(EPSILON)));
+                        tree.add_edge(node, leaf, None);
+                    }
+                    for (_, child) in children {
+                        tree.add_edge(node, child, None);
+                    }
+                    symbols.push((lhs, node));
+
+                    // the goto of a reduction always exists, the state was reached by a prefix of α
+                    let to = self.get_goto(*states.last().unwrap(), production.get_lhs()).unwrap();
+                    states.push(to);
+                    step.action = format!("reduce {}, go to {}", grammar.production_to_string(*index), to);
+                }
+                Some(LRAction::Accept) => {
+                    step.action = "accept".to_string();
+                    tree.start_node = symbols.last().map(|(_, node)| *node);
+                    steps.push(step);
+                    break;
+                }
+                None => {
+                    let error = format!("unexpected {} in state {}", grammar.terminal_to_string(next), state);
+                    step.action = format!("error: {}", error);
+                    errors.push(error);
+                    steps.push(step);
+                    break;
+                }
+            }
+            steps.push(step);
+        }
+
+        Ok(ParseResult { steps, tree, errors })
+    }
+}
+
+impl Grammar {
+    /// adds to the items [B -> . γ, b] for every item [A -> α . B β, a] in the set
+    /// and every b in FIRST(β a), until nothing changes
+    fn lr1_closure(&mut self, items: &LR1ItemSet) -> LR1ItemSet {
+        let mut closure = items.clone();
+        let mut to_visit: Vec<(Item, Terminal)> = items
+            .iter()
+            .flat_map(|(item, lookaheads)| lookaheads.iter().map(move |lookahead| (*item, *lookahead)))
+            .collect();
+
+        while let Some((item, lookahead)) = to_visit.pop() {
+            let non_terminal = match self.get_next_letter(&item) {
+                Some(Letter::NonTerminal(non_terminal)) => *non_terminal,
+                _ => continue,
+            };
+            let mut rest = self.get_productions()[item.production].get_rhs()[item.dot + 1..].to_vec();
+            rest.push(Letter::Terminal(lookahead));
+            let first = self.first_of_sequence(&rest);
+
+            for (index, production) in self.get_productions().iter().enumerate() {
+                if production.get_lhs() != non_terminal {
+                    continue;
+                }
+                let new_item = Item { production: index, dot: 0 };
+                for terminal in first.iter() {
+                    if closure.entry(new_item).or_default().insert(*terminal) {
+                        to_visit.push((new_item, *terminal));
+                    }
+                }
+            }
+        }
+
+        closure
+    }
+
+    /// moves the dot over the letter, keeping the lookaheads, and returns the closure
+    fn lr1_goto(&mut self, items: &LR1ItemSet, letter: &Letter) -> LR1ItemSet {
+        let moved: LR1ItemSet = items
+            .iter()
+            .filter(|(item, _)| self.get_next_letter(item) == Some(letter))
+            .map(|(item, lookaheads)| {
+                let item = Item {
+                    production: item.production,
+                    dot: item.dot + 1,
+                };
+                (item, lookaheads.clone())
+            })
+            .collect();
+
+        self.lr1_closure(&moved)
+    }
+
+    /// builds the canonical collection of LR(1) items of the augmented grammar
+    fn get_lr1_states(&self) -> (Grammar, Vec<LR1ItemSet>, Vec<BTreeMap<Letter, usize>>) {
+        let mut grammar = self.get_augmented();
+        let start_item = Item {
+            production: grammar.get_productions().len() - 1,
+            dot: 0,
+        };
+
+        let start = LR1ItemSet::from([(start_item, BTreeSet::from([STRING_END]))]);
+        let start = grammar.lr1_closure(&start);
+        let (states, transitions) = canonical_collection(start, |items: &LR1ItemSet| {
+            let letters: BTreeSet<Letter> = items
+                .keys()
+                .filter_map(|item| grammar.get_next_letter(item).cloned())
+                .collect();
+            letters
+                .into_iter()
+                .map(|letter| {
+                    let next = grammar.lr1_goto(items, &letter);
+                    (letter, next)
+                })
+                .collect()
+        });
+
+        (grammar, states, transitions)
+    }
+
+    /// builds the LR table of the given kind, see `LRKind`
+    pub fn get_lr_table(&self, kind: LRKind) -> LRTable {
+        match kind {
+            LRKind::SLR1 => {
+                let automaton = self.get_lr0_automaton();
+                let mut grammar = automaton.get_grammar().clone();
+                let follow = grammar.get_follow_table().clone();
+                let states = automaton
+                    .get_states()
+                    .iter()
+                    .map(|items| {
+                        items
+                            .iter()
+                            .map(|item| {
+                                let lhs = grammar.get_productions()[item.production].get_lhs();
+                                (*item, follow[&lhs].clone())
+                            })
+                            .collect()
+                    })
+                    .collect();
+                LRTable::new(kind, grammar, states, automaton.get_transitions().clone())
+            }
+            LRKind::LR1 => {
+                let (grammar, states, transitions) = self.get_lr1_states();
+                LRTable::new(kind, grammar, states, transitions)
+            }
+            LRKind::LALR1 => {
+                let (grammar, states, transitions) = self.get_lr1_states();
+
+                // the states with the same core are merged, in order of first appearance
+                let mut cores: Vec<BTreeSet<Item>> = vec![];
+                let mut merged_state = vec![];
+                for items in states.iter() {
+                    let core: BTreeSet<Item> = items.keys().cloned().collect();
+                    match cores.iter().position(|other| *other == core) {
+                        Some(index) => merged_state.push(index),
+                        None => {
+                            cores.push(core);
+                            merged_state.push(cores.len() - 1);
+                        }
+                    }
+                }
+
+                let mut merged_states = vec![LR1ItemSet::new(); cores.len()];
+                let mut merged_transitions = vec![BTreeMap::new(); cores.len()];
+                for (state, items) in states.into_iter().enumerate() {
+                    let merged = &mut merged_states[merged_state[state]];
+                    for (item, lookaheads) in items {
+                        merged.entry(item).or_default().extend(lookaheads);
+                    }
+                    // the goto of states with the same core have the same core
+                    for (letter, to) in transitions[state].iter() {
+                        merged_transitions[merged_state[state]].insert(letter.clone(), merged_state[*to]);
+                    }
+                }
+                LRTable::new(kind, grammar, merged_states, merged_transitions)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grammar::test_utils::expression_grammar;
+
+    fn conflicts(text: &str, kind: LRKind) -> Vec<LRConflict> {
+        Grammar::from_text(text).unwrap().get_lr_table(kind).get_conflicts()
+    }
+
+    #[test]
+    fn expression_grammar_table() {
+        let grammar = expression_grammar();
+
+        let slr = grammar.get_lr_table(LRKind::SLR1);
+        assert!(!slr.has_conflicts());
+        assert_eq!(slr.get_num_states(), 12);
+        // state 0 shifts id, and after it F -> id is reduced on +
+        let id = grammar.get_symbols().get_terminal("id").unwrap();
+        let plus = grammar.get_symbols().get_terminal("+").unwrap();
+        let after_id = match slr.get_actions(0, id) {
+            [LRAction::Shift(state)] => *state,
+            actions => panic!("expected a shift, found {:?}", actions),
+        };
+        assert_eq!(slr.get_actions(after_id, plus), &[LRAction::Reduce(5)]);
+        assert_eq!(slr.get_actions(0, plus), &[]);
+        assert_eq!(slr.get_goto(0, 0), Some(1));
+        assert_eq!(slr.get_non_terminals(), vec![0, 1, 2]);
+
+        assert_eq!(grammar.get_lr_table(LRKind::LALR1).get_num_states(), 12);
+        assert_eq!(grammar.get_lr_table(LRKind::LR1).get_num_states(), 22);
+        assert!(!grammar.get_lr_table(LRKind::LR1).has_conflicts());
+    }
+
+    #[test]
+    fn slr_shift_reduce_conflict() {
+        // the assignment grammar is LALR(1) but not SLR(1), since = is in FOLLOW(R)
+        let text = "
+            S -> L = R | R
+            L -> * R | id
+            R -> L
+        ";
+        let slr_conflicts = conflicts(text, LRKind::SLR1);
+        assert_eq!(slr_conflicts.len(), 1);
+        assert!(slr_conflicts[0].is_shift_reduce());
+        assert_eq!(slr_conflicts[0].items, vec!["S -> L . = R", "R -> L ."]);
+
+        assert!(conflicts(text, LRKind::LALR1).is_empty());
+        assert!(conflicts(text, LRKind::LR1).is_empty());
+
+        let grammar = Grammar::from_text(text).unwrap();
+        assert_eq!(grammar.get_lr_table(LRKind::LALR1).get_num_states(), 10);
+        assert_eq!(grammar.get_lr_table(LRKind::LR1).get_num_states(), 14);
+    }
+
+    #[test]
+    fn lalr_reduce_reduce_conflict() {
+        // merging the states of c in the two contexts mixes the lookaheads
+        let text = "
+            S -> a A d | b B d | a B e | b A e
+            A -> c
+            B -> c
+        ";
+        assert!(conflicts(text, LRKind::LR1).is_empty());
+
+        let lalr_conflicts = conflicts(text, LRKind::LALR1);
+        assert_eq!(lalr_conflicts.len(), 2);
+        assert!(!lalr_conflicts[0].is_shift_reduce());
+        assert_eq!(lalr_conflicts[0].actions, vec![LRAction::Reduce(4), LRAction::Reduce(5)]);
+        assert_eq!(lalr_conflicts[0].items, vec!["[A -> c ., d/e]", "[B -> c ., d/e]"]);
+    }
+
+    #[test]
+    fn shift_reduce_parser() {
+        let grammar = expression_grammar();
+        let table = grammar.get_lr_table(LRKind::SLR1);
+        let result = table.parse(&grammar.tokenize("id * id + id").unwrap()).unwrap();
+
+        assert!(result.errors.is_empty());
+        let first = &result.steps[0];
+        assert_eq!(first.stack, "0");
+        assert_eq!(first.symbols.as_deref(), Some("$"));
+        assert_eq!(first.input, "id * id + id $");
+        assert_eq!(result.steps[1].action, "reduce F -> id, go to 3");
+        assert_eq!(result.steps[1].stack, "0 5");
+        assert_eq!(result.steps[2].symbols.as_deref(), Some("$ F"));
+        let last = result.steps.last().unwrap();
+        assert_eq!((last.stack.as_str(), last.action.as_str()), ("0 1", "accept"));
+
+        // the root is the last reduced node, E -> E + T
+        let root = result.tree.start_node.unwrap();
+        assert_eq!(result.tree.get_node_label(root).as_deref(), Some("E"));
+        assert_eq!(result.tree.get_node_edges(root).len(), 3);
+        // 5 terminals and 8 reductions
+        assert_eq!(result.tree.get_nodes().len(), 13);
+    }
+
+    #[test]
+    fn parser_errors() {
+        let grammar = Grammar::from_text("S -> a S b | ε").unwrap();
+        for kind in LRKind::ALL {
+            let table = grammar.get_lr_table(kind);
+            let result = table.parse(&grammar.tokenize("a a b b").unwrap()).unwrap();
+            assert!(result.errors.is_empty());
+            assert_eq!(result.tree.get_nodes().iter().filter(|node| node.label.as_deref() == Some("ε")).count(), 1);
+
+            let result = table.parse(&grammar.tokenize("a b b").unwrap()).unwrap();
+            assert_eq!(result.errors.len(), 1);
+            assert!(result.steps.last().unwrap().action.starts_with("error: unexpected b"));
+        }
+
+        let ambiguous = Grammar::from_text("E -> E + E | id").unwrap();
+        assert!(ambiguous.get_lr_table(LRKind::LR1).parse(&[]).is_err());
+    }
+
+    #[test]
+    fn ambiguous_grammar() {
+        for kind in LRKind::ALL {
+            let found = conflicts("E -> E + E | id", kind);
+            assert!(!found.is_empty());
+            assert!(found.iter().all(|conflict| conflict.is_shift_reduce()));
+        }
+    }
+
+    #[test]
+    fn accept_reduce_conflict() {
+        // after S both S' -> S . and S -> S . are complete
+        let slr_conflicts = conflicts("S -> S | a", LRKind::SLR1);
+        assert_eq!(slr_conflicts.len(), 1);
+        assert_eq!(slr_conflicts[0].actions, vec![LRAction::Reduce(0), LRAction::Accept]);
+        assert_eq!(slr_conflicts[0].items, vec!["S -> S .", "S' -> S ."]);
+    }
+}
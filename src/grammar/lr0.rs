@@ -0,0 +1,211 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::grammar::{Grammar, Item, Letter};
+use crate::utils::Graph;
+
+pub type ItemSet = BTreeSet<Item>;
+
+/// LR(0) automaton of the augmented grammar: the states are the sets of items of the
+/// canonical collection, reading a letter moves from a set I to goto(I, letter).
+/// The state 0 is the closure of S' -> . S
+#[derive(Debug, Clone)]
+pub struct LR0Automaton {
+    grammar: Grammar,
+    states: Vec<ItemSet>,
+    transitions: Vec<BTreeMap<Letter, usize>>,
+}
+
+impl LR0Automaton {
+    /// returns the augmented grammar, the items refer to its productions
+    pub fn get_grammar(&self) -> &Grammar {
+        &self.grammar
+    }
+
+    pub fn get_states(&self) -> &Vec<ItemSet> {
+        &self.states
+    }
+
+    pub fn get_transitions(&self) -> &Vec<BTreeMap<Letter, usize>> {
+        &self.transitions
+    }
+
+    /// shows the items of the state one for each line
+    pub fn state_to_string(&self, state: usize) -> String {
+        self.states[state]
+            .iter()
+            .map(|item| self.grammar.item_to_string(item))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl Grammar {
+    /// adds to the items B -> . γ for every item A -> α . B β in the set, until nothing changes
+    pub fn closure(&self, items: &ItemSet) -> ItemSet {
+        let mut closure = items.clone();
+        let mut to_visit: Vec<Item> = items.iter().cloned().collect();
+        while let Some(item) = to_visit.pop() {
+            let non_terminal = match self.get_next_letter(&item) {
+                Some(Letter::NonTerminal(non_terminal)) => *non_terminal,
+                _ => continue,
+            };
+
+            for (index, production) in self.get_productions().iter().enumerate() {
+                let new_item = Item { production: index, dot: 0 };
+                if production.get_lhs() == non_terminal && closure.insert(new_item) {
+                    to_visit.push(new_item);
+                }
+            }
+        }
+
+        closure
+    }
+
+    /// moves the dot over the letter in the items that have it after the dot,
+    /// and returns the closure of the moved items
+    pub fn goto(&self, items: &ItemSet, letter: &Letter) -> ItemSet {
+        let moved: ItemSet = items
+            .iter()
+            .filter(|item| self.get_next_letter(item) == Some(letter))
+            .map(|item| Item {
+                production: item.production,
+                dot: item.dot + 1,
+            })
+            .collect();
+
+        self.closure(&moved)
+    }
+
+    /// builds the canonical collection of LR(0) items of the augmented grammar,
+    /// visiting the goto of each state in breadth first order
+    pub fn get_lr0_automaton(&self) -> LR0Automaton {
+        let grammar = self.get_augmented();
+        let start_item = Item {
+            production: grammar.get_productions().len() - 1,
+            dot: 0,
+        };
+
+        let start = grammar.closure(&BTreeSet::from([start_item]));
+        let (states, transitions) = canonical_collection(start, |items: &ItemSet| {
+            let letters: BTreeSet<Letter> = items
+                .iter()
+                .filter_map(|item| grammar.get_next_letter(item).cloned())
+                .collect();
+            letters
+                .into_iter()
+                .map(|letter| {
+                    let next = grammar.goto(items, &letter);
+                    (letter, next)
+                })
+                .collect()
+        });
+
+        LR0Automaton {
+            grammar,
+            states,
+            transitions,
+        }
+    }
+}
+
+/// builds the states reachable from the start state, visiting the successors of each
+/// state in breadth first order, with the transitions between them.
+/// The successors are the pairs (letter, goto on the letter) of a state
+pub fn canonical_collection<S: PartialEq>(
+    start: S,
+    mut successors: impl FnMut(&S) -> Vec<(Letter, S)>,
+) -> (Vec<S>, Vec<BTreeMap<Letter, usize>>) {
+    let mut states = vec![start];
+    let mut transitions = vec![];
+    let mut state = 0;
+    while state < states.len() {
+        let mut state_transitions = BTreeMap::new();
+        for (letter, next) in successors(&states[state]) {
+            let next_state = match states.iter().position(|other| *other == next) {
+                Some(next_state) => next_state,
+                None => {
+                    states.push(next);
+                    states.len() - 1
+                }
+            };
+            state_transitions.insert(letter, next_state);
+        }
+        transitions.push(state_transitions);
+        state += 1;
+    }
+
+    (states, transitions)
+}
+
+impl From<&LR0Automaton> for Graph {
+    /// each node shows the name of the state and its items
+    fn from(automaton: &LR0Automaton) -> Self {
+        let mut graph = Graph::new();
+        for state in 0..automaton.states.len() {
+            graph.add_node(Some(format!("I{}\n{}", state, automaton.state_to_string(state))));
+        }
+        for (from, transitions) in automaton.transitions.iter().enumerate() {
+            for (letter, to) in transitions.iter() {
+                graph.add_edge(from, *to, Some(automaton.grammar.letter_to_string(letter)));
+            }
+        }
+        graph.start_node = Some(0);
+        graph
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grammar::test_utils::expression_grammar;
+
+    #[test]
+    fn closure_and_goto() {
+        let grammar = expression_grammar().get_augmented();
+        let start = grammar.closure(&BTreeSet::from([Item { production: 6, dot: 0 }]));
+
+        let items: BTreeSet<String> = start.iter().map(|item| grammar.item_to_string(item)).collect();
+        assert_eq!(items.len(), 7);
+        assert!(items.contains("E' -> . E"));
+        assert!(items.contains("F -> . ( E )"));
+
+        let plus = Letter::Terminal(grammar.get_symbols().get_terminal("+").unwrap());
+        let e = Letter::NonTerminal(grammar.get_symbols().get_non_terminal("E").unwrap());
+        let goto: Vec<String> = grammar
+            .goto(&start, &e)
+            .iter()
+            .map(|item| grammar.item_to_string(item))
+            .collect();
+        assert_eq!(goto, vec!["E -> E . + T", "E' -> E ."]);
+        assert!(grammar.goto(&start, &plus).is_empty());
+    }
+
+    #[test]
+    fn canonical_collection() {
+        // the classic 12 states of the expression grammar
+        let automaton = expression_grammar().get_lr0_automaton();
+
+        assert_eq!(automaton.get_states().len(), 12);
+        assert_eq!(automaton.get_grammar().get_start_symbol(), 3);
+        assert_eq!(automaton.state_to_string(0).lines().next(), Some("E -> . E + T"));
+
+        // every state is reached by a single letter, the one before the dot of its kernel
+        let transitions = automaton.get_transitions();
+        let num_transitions: usize = transitions.iter().map(|transitions| transitions.len()).sum();
+        assert_eq!(num_transitions, 22);
+
+        let graph: Graph = (&automaton).into();
+        assert_eq!(graph.get_nodes().len(), 12);
+        assert_eq!(graph.get_edges_ids().len(), 22);
+    }
+
+    #[test]
+    fn epsilon_productions() {
+        // the ε production is complete as soon as it's added by the closure
+        let grammar = Grammar::from_text("S -> a S b | ε").unwrap();
+        let automaton = grammar.get_lr0_automaton();
+
+        assert_eq!(automaton.state_to_string(0), "S -> . a S b\nS -> .\nS' -> . S");
+        assert_eq!(automaton.get_states().len(), 5);
+    }
+}
@@ -1,3 +1,17 @@
+mod arden;
 mod grammar;
+mod ll1;
+mod lr;
+mod lr0;
+mod parser;
+mod symbols;
+#[cfg(test)]
+pub(crate) mod test_utils;
+mod trace;
 
-pub use grammar::*;
\ No newline at end of file
+pub use arden::*;
+pub use grammar::*;
+pub use ll1::*;
+pub use lr::*;
+pub use symbols::*;
+pub use trace::*;
@@ -0,0 +1,328 @@
+use std::error::Error;
+
+use crate::error::GrammarSyntaxError;
+use crate::grammar::{Grammar, Letter, Production, SymbolTable, EPSILON};
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    /// "->", "→" or "::="
+    Arrow,
+    Pipe,
+    /// a name, it's a non terminal if it appears on the left of a rule
+    Symbol(String),
+    /// a terminal between quotes, it can contain the special characters
+    Quoted(String),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+    column: usize,
+}
+
+/// a rule "lhs -> alternatives", with the continuation lines that start with '|'
+struct Rule {
+    lhs: Token,
+    alternatives: Vec<Vec<Token>>,
+}
+
+/// a quote only starts a quoted terminal at the start of a symbol, so names like E' are allowed
+fn is_symbol_char(chars: &[char], i: usize) -> bool {
+    let ch = chars[i];
+    let is_arrow = ch == '-' && chars.get(i + 1) == Some(&'>');
+    !(ch.is_whitespace() || is_arrow || matches!(ch, '|' | '#' | '"' | '→'))
+}
+
+/// splits a line in tokens, a '#' outside quotes starts a comment
+fn tokenize_line(line: &str, line_number: usize) -> Result<Vec<Token>, GrammarSyntaxError> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let column = i + 1;
+        let token = |kind| Token {
+            kind,
+            line: line_number,
+            column,
+        };
+
+        match chars[i] {
+            ch if ch.is_whitespace() => i += 1,
+            '#' => break,
+            '|' => {
+                tokens.push(token(TokenKind::Pipe));
+                i += 1;
+            }
+            '→' => {
+                tokens.push(token(TokenKind::Arrow));
+                i += 1;
+            }
+            '-' if chars.get(i + 1) == Some(&'>') => {
+                tokens.push(token(TokenKind::Arrow));
+                i += 2;
+            }
+            ':' if chars[i..].starts_with(&[':', ':', '=']) => {
+                tokens.push(token(TokenKind::Arrow));
+                i += 3;
+            }
+            quote @ ('\'' | '"') => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|ch| *ch == quote)
+                    .map(|len| i + 1 + len)
+                    .ok_or_else(|| {
+                        GrammarSyntaxError::new(line_number, column, "unterminated quoted terminal".to_string())
+                    })?;
+                if end == i + 1 {
+                    return Err(GrammarSyntaxError::new(line_number, column, "empty quoted terminal".to_string()));
+                }
+                tokens.push(token(TokenKind::Quoted(chars[i + 1..end].iter().collect())));
+                i = end + 1;
+            }
+            _ => {
+                let start = i;
+                while i < chars.len() && is_symbol_char(&chars, i) {
+                    i += 1;
+                }
+                tokens.push(token(TokenKind::Symbol(chars[start..i].iter().collect())));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// groups the tokens of the lines in rules, splitting the alternatives at the '|'
+fn get_rules(text: &str) -> Result<Vec<Rule>, GrammarSyntaxError> {
+    let mut rules: Vec<Rule> = vec![];
+
+    for (index, line) in text.lines().enumerate() {
+        let tokens = tokenize_line(line, index + 1)?;
+        let mut tokens = tokens.into_iter().peekable();
+        let first = match tokens.next() {
+            Some(first) => first,
+            None => continue,
+        };
+
+        let mut alternatives = vec![vec![]];
+        match &first.kind {
+            // continuation of the previous rule
+            TokenKind::Pipe => match rules.last_mut() {
+                Some(rule) => {
+                    for token in tokens {
+                        match token.kind {
+                            TokenKind::Pipe => alternatives.push(vec![]),
+                            _ => alternatives.last_mut().unwrap().push(token),
+                        }
+                    }
+                    rule.alternatives.append(&mut alternatives);
+                    continue;
+                }
+                None => {
+                    return Err(GrammarSyntaxError::new(
+                        first.line,
+                        first.column,
+                        "'|' without a rule to continue".to_string(),
+                    ))
+                }
+            },
+            TokenKind::Symbol(_) => {}
+            _ => {
+                return Err(GrammarSyntaxError::new(
+                    first.line,
+                    first.column,
+                    "expected a non terminal at the start of the rule".to_string(),
+                ))
+            }
+        }
+
+        match tokens.next() {
+            Some(Token {
+                kind: TokenKind::Arrow,
+                ..
+            }) => {}
+            Some(token) => {
+                return Err(GrammarSyntaxError::new(
+                    token.line,
+                    token.column,
+                    "expected '->' after the non terminal".to_string(),
+                ))
+            }
+            None => {
+                return Err(GrammarSyntaxError::new(
+                    first.line,
+                    first.column + line.chars().skip(first.column - 1).count(),
+                    "expected '->' after the non terminal".to_string(),
+                ))
+            }
+        }
+
+        for token in tokens {
+            match token.kind {
+                TokenKind::Pipe => alternatives.push(vec![]),
+                _ => alternatives.last_mut().unwrap().push(token),
+            }
+        }
+        rules.push(Rule {
+            lhs: first,
+            alternatives,
+        });
+    }
+
+    Ok(rules)
+}
+
+/// converts a symbol of an alternative to a letter, only the names on the
+/// left of a rule are non terminals, the other symbols are added to the terminals
+fn get_letter(token: &Token, symbols: &mut SymbolTable) -> Result<Letter, GrammarSyntaxError> {
+    match &token.kind {
+        TokenKind::Symbol(name) => match symbols.get_non_terminal(name) {
+            Some(non_terminal) => Ok(Letter::NonTerminal(non_terminal)),
+            None => Ok(Letter::Terminal(symbols.add_terminal(name))),
+        },
+        TokenKind::Quoted(name) => Ok(Letter::Terminal(symbols.add_terminal(name))),
+        _ => Err(GrammarSyntaxError::new(
+            token.line,
+            token.column,
+            "unexpected '->' in the right hand side".to_string(),
+        )),
+    }
+}
+
+impl Grammar {
+    /// Parses a grammar written in a BNF like notation, one rule for each line:
+    ///
+    /// E -> E + T | T
+    /// T -> T * F
+    ///    | F          # a line starting with '|' continues the previous rule
+    /// F -> ( E ) | 'a'
+    /// A -> a A | ε    # ε or an empty alternative is the empty string
+    ///
+    /// Symbols are separated by spaces, the names on the left of a rule are
+    /// the non terminals and the first one is the start symbol. The other
+    /// symbols are terminals, like id or :=, quotes allow terminals like '|' or '#'.
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let rules = get_rules(text)?;
+        if rules.is_empty() {
+            return Err(Box::new(GrammarSyntaxError::new(1, 1, "the grammar has no rules".to_string())));
+        }
+
+        let mut symbols = SymbolTable::new();
+        for rule in rules.iter() {
+            if let TokenKind::Symbol(name) = &rule.lhs.kind {
+                symbols.add_non_terminal(name);
+            }
+        }
+
+        let mut productions = vec![];
+        for rule in rules.iter() {
+            let lhs = match &rule.lhs.kind {
+                TokenKind::Symbol(name) => symbols.get_non_terminal(name).unwrap(),
+                _ => unreachable!(),
+            };
+
+            for alternative in rule.alternatives.iter() {
+                let is_epsilon = |token: &Token| token.kind == TokenKind::Symbol("ε".to_string());
+                let rhs = match alternative.iter().find(|token| is_epsilon(token)) {
+                    None if alternative.is_empty() => vec![Letter::Terminal(EPSILON)],
+                    None => alternative
+                        .iter()
+                        .map(|token| get_letter(token, &mut symbols))
+                        .collect::<Result<Vec<Letter>, GrammarSyntaxError>>()?,
+                    Some(_) if alternative.len() == 1 => vec![Letter::Terminal(EPSILON)],
+                    Some(token) => {
+                        return Err(Box::new(GrammarSyntaxError::new(
+                            token.line,
+                            token.column,
+                            "ε must be alone in its alternative".to_string(),
+                        )))
+                    }
+                };
+                productions.push(Production::new(lhs, rhs));
+            }
+        }
+
+        Ok(Grammar::new(0, productions, symbols))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn syntax_error(text: &str) -> (usize, usize) {
+        let error = Grammar::from_text(text).unwrap_err();
+        let error = error.downcast_ref::<GrammarSyntaxError>().unwrap();
+        (error.line, error.column)
+    }
+
+    #[test]
+    fn expression_grammar() {
+        let text = "
+            # arithmetic expressions
+            E -> E + T | T
+            T -> T * F
+               | F
+            F -> ( E ) | 'id'
+        ";
+        let grammar = Grammar::from_text(text).unwrap();
+        let symbols = grammar.get_symbols();
+
+        let (e, t, f) = (0, 1, 2);
+        assert_eq!(symbols.get_non_terminal_name(e), "E");
+        assert_eq!(symbols.get_non_terminal("F"), Some(f));
+        let terminal = |name| Letter::Terminal(symbols.get_terminal(name).unwrap());
+        assert_eq!(
+            grammar.get_productions(),
+            &vec![
+                Production::new(e, vec![Letter::NonTerminal(e), terminal("+"), Letter::NonTerminal(t)]),
+                Production::new(e, vec![Letter::NonTerminal(t)]),
+                Production::new(t, vec![Letter::NonTerminal(t), terminal("*"), Letter::NonTerminal(f)]),
+                Production::new(t, vec![Letter::NonTerminal(f)]),
+                Production::new(f, vec![terminal("("), Letter::NonTerminal(e), terminal(")")]),
+                Production::new(f, vec![terminal("id")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn epsilon_and_special_terminals() {
+        let grammar = Grammar::from_text("S → A '|' S | ε\nA ::= a A |").unwrap();
+        let symbols = grammar.get_symbols();
+
+        let (s, a) = (0, 1);
+        assert_eq!(symbols.get_num_non_terminals(), 2);
+        let terminal = |name| Letter::Terminal(symbols.get_terminal(name).unwrap());
+        assert_eq!(
+            grammar.get_productions(),
+            &vec![
+                Production::new(s, vec![Letter::NonTerminal(a), terminal("|"), Letter::NonTerminal(s)]),
+                Production::new(s, vec![Letter::Terminal(EPSILON)]),
+                Production::new(a, vec![terminal("a"), Letter::NonTerminal(a)]),
+                Production::new(a, vec![Letter::Terminal(EPSILON)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn diagnostics() {
+        assert_eq!(syntax_error("S -> a\n  S a"), (2, 5));
+        assert_eq!(syntax_error("| a"), (1, 1));
+        assert_eq!(syntax_error("S -> 'a"), (1, 6));
+        assert_eq!(syntax_error("S -> a ε"), (1, 8));
+        assert_eq!(syntax_error("S -> a -> b"), (1, 8));
+        assert_eq!(syntax_error("# only a comment"), (1, 1));
+    }
+
+    #[test]
+    fn primed_non_terminals() {
+        let grammar = Grammar::from_text("E -> T E'\nE' -> + T E' | ε\nT -> 'id'").unwrap();
+        let symbols = grammar.get_symbols();
+
+        assert_eq!(symbols.get_non_terminal("E'"), Some(1));
+        assert_eq!(symbols.get_terminal("id").map(|id| symbols.get_terminal_name(id)), Some("id"));
+        assert_eq!(grammar.get_productions().len(), 4);
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+
+use crate::grammar::{NonTerminal, Terminal};
+
+/// Symbol table of a grammar, it maps the names of the non terminals and of the
+/// terminals to the compact ids used inside `Grammar`.
+///
+/// The first terminal ids are reserved for ε and for the end of the string $,
+/// their names are not in the table so a terminal named "$" gets its own id.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolTable {
+    non_terminals: Vec<String>,
+    non_terminal_ids: BTreeMap<String, NonTerminal>,
+    terminals: Vec<String>,
+    terminal_ids: BTreeMap<String, Terminal>,
+}
+
+/// names of the reserved terminals, in order of id
+const RESERVED_TERMINALS: [&str; 2] = ["ε", "$"];
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self {
+            non_terminals: vec![],
+            non_terminal_ids: BTreeMap::new(),
+            terminals: RESERVED_TERMINALS.iter().map(|name| name.to_string()).collect(),
+            terminal_ids: BTreeMap::new(),
+        }
+    }
+
+    /// returns the id of the non terminal, the ids are given in order of insertion
+    pub fn add_non_terminal(&mut self, name: &str) -> NonTerminal {
+        if let Some(id) = self.non_terminal_ids.get(name) {
+            return *id;
+        }
+        self.non_terminals.push(name.to_string());
+        self.non_terminal_ids.insert(name.to_string(), self.non_terminals.len() - 1);
+        self.non_terminals.len() - 1
+    }
+
+    /// returns the id of the terminal, the ids are given in order of insertion
+    /// after the reserved ones
+    pub fn add_terminal(&mut self, name: &str) -> Terminal {
+        if let Some(id) = self.terminal_ids.get(name) {
+            return *id;
+        }
+        self.terminals.push(name.to_string());
+        self.terminal_ids.insert(name.to_string(), self.terminals.len() - 1);
+        self.terminals.len() - 1
+    }
+
+    pub fn get_non_terminal(&self, name: &str) -> Option<NonTerminal> {
+        self.non_terminal_ids.get(name).cloned()
+    }
+
+    pub fn get_terminal(&self, name: &str) -> Option<Terminal> {
+        self.terminal_ids.get(name).cloned()
+    }
+
+    pub fn get_non_terminal_name(&self, non_terminal: NonTerminal) -> &str {
+        &self.non_terminals[non_terminal]
+    }
+
+    pub fn get_terminal_name(&self, terminal: Terminal) -> &str {
+        &self.terminals[terminal]
+    }
+
+    pub fn get_num_non_terminals(&self) -> usize {
+        self.non_terminals.len()
+    }
+
+    /// returns the ids of the terminals, the reserved ones excluded
+    pub fn get_terminals(&self) -> Vec<Terminal> {
+        (RESERVED_TERMINALS.len()..self.terminals.len()).collect()
+    }
+}
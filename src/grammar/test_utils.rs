@@ -0,0 +1,29 @@
+use crate::grammar::{Grammar, NonTerminal, Terminal};
+
+pub fn terminal(grammar: &Grammar, name: &str) -> Terminal {
+    grammar.get_symbols().get_terminal(name).unwrap()
+}
+
+pub fn non_terminal(grammar: &Grammar, name: &str) -> NonTerminal {
+    grammar.get_symbols().get_non_terminal(name).unwrap()
+}
+
+/// the left recursive grammar of the arithmetic expressions
+pub fn expression_grammar() -> Grammar {
+    Grammar::from_text("
+        E -> E + T | T
+        T -> T * F | F
+        F -> ( E ) | id
+    ").unwrap()
+}
+
+/// the expression grammar without left recursion, it's LL(1)
+pub fn ll_expression_grammar() -> Grammar {
+    Grammar::from_text("
+        E -> T E'
+        E' -> + T E' | ε
+        T -> F T'
+        T' -> * F T' | ε
+        F -> ( E ) | id
+    ").unwrap()
+}
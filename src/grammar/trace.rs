@@ -0,0 +1,45 @@
+use std::error::Error;
+
+use crate::error::UnknownTerminal;
+use crate::grammar::{Grammar, Terminal};
+use crate::utils::Graph;
+
+/// a step of a parser: the stack (the top on the right), the input left to
+/// read and the action done on them. The LR parsers keep the states in the stack
+/// and the symbols in a second stack
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseStep {
+    pub stack: String,
+    pub symbols: Option<String>,
+    pub input: String,
+    pub action: String,
+}
+
+/// the steps of a parser and the parse tree it built, with the syntax
+/// errors found and recovered on the way. The input is accepted if there are no errors
+pub struct ParseResult {
+    pub steps: Vec<ParseStep>,
+    pub tree: Graph,
+    pub errors: Vec<String>,
+}
+
+impl Grammar {
+    /// splits the input at the whitespace, every word must be the name of a terminal
+    pub fn tokenize(&self, input: &str) -> Result<Vec<Terminal>, Box<dyn Error>> {
+        input
+            .split_whitespace()
+            .map(|name| {
+                self.get_symbols()
+                    .get_terminal(name)
+                    .ok_or_else(|| Box::new(UnknownTerminal::new(name.to_string())) as Box<dyn Error>)
+            })
+            .collect()
+    }
+
+    /// shows the terminals separated by spaces, ending with $
+    pub fn input_to_string(&self, input: &[Terminal]) -> String {
+        let mut names: Vec<String> = input.iter().map(|terminal| self.terminal_to_string(*terminal)).collect();
+        names.push("$".to_string());
+        names.join(" ")
+    }
+}
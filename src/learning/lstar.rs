@@ -0,0 +1,274 @@
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
+use crate::automata::{word_to_string, DFA};
+use crate::learning::Oracle;
+
+/// Observation table of L*: the rows are the prefixes S and their one
+/// character extensions S·Σ, the columns are the suffixes E, and the entry
+/// (s, e) says if the word se is in the language.
+///
+/// S is prefix closed and E is suffix closed, both contain the empty word.
+#[derive(Debug, Clone)]
+pub struct ObservationTable {
+    alphabet: Vec<char>,
+    prefixes: Vec<String>,
+    suffixes: Vec<String>,
+    // answers of the membership queries asked so far
+    memberships: BTreeMap<String, bool>,
+}
+
+impl ObservationTable {
+    fn new(alphabet: Vec<char>) -> Self {
+        Self {
+            alphabet,
+            prefixes: vec![String::new()],
+            suffixes: vec![String::new()],
+            memberships: BTreeMap::new(),
+        }
+    }
+
+    pub fn get_prefixes(&self) -> &Vec<String> {
+        &self.prefixes
+    }
+
+    pub fn get_suffixes(&self) -> &Vec<String> {
+        &self.suffixes
+    }
+
+    /// returns the words of S·Σ that are not in S
+    pub fn get_extended_prefixes(&self) -> Vec<String> {
+        let mut extended = vec![];
+        for prefix in self.prefixes.iter() {
+            for ch in self.alphabet.iter() {
+                let word = format!("{}{}", prefix, ch);
+                if !self.prefixes.contains(&word) && !extended.contains(&word) {
+                    extended.push(word);
+                }
+            }
+        }
+        extended
+    }
+
+    pub fn get_num_membership_queries(&self) -> usize {
+        self.memberships.len()
+    }
+
+    /// asks the oracle the missing entries of the table
+    fn fill<O: Oracle>(&mut self, oracle: &O) {
+        let rows: Vec<String> = self
+            .prefixes
+            .iter()
+            .cloned()
+            .chain(self.get_extended_prefixes())
+            .collect();
+
+        for row in rows.iter() {
+            for suffix in self.suffixes.iter() {
+                let word = format!("{}{}", row, suffix);
+                if let Entry::Vacant(entry) = self.memberships.entry(word) {
+                    let answer = oracle.membership(entry.key());
+                    entry.insert(answer);
+                }
+            }
+        }
+    }
+
+    /// returns the entries of the row of the word, it must be in S or in S·Σ
+    pub fn row(&self, word: &str) -> Vec<bool> {
+        self.suffixes
+            .iter()
+            .map(|suffix| self.memberships[&format!("{}{}", word, suffix)])
+            .collect()
+    }
+
+    /// the table is closed if every row of S·Σ is also a row of S,
+    /// returns the first extended prefix with a new row
+    fn find_unclosed(&self) -> Option<String> {
+        let rows: Vec<Vec<bool>> = self.prefixes.iter().map(|prefix| self.row(prefix)).collect();
+        self.get_extended_prefixes()
+            .into_iter()
+            .find(|word| !rows.contains(&self.row(word)))
+    }
+
+    /// the table is consistent if two prefixes with the same row still have the same
+    /// rows after reading any character. Returns the suffix ae that tells them apart.
+    fn find_inconsistency(&self) -> Option<String> {
+        for (i, first) in self.prefixes.iter().enumerate() {
+            for second in self.prefixes[i + 1..].iter() {
+                if self.row(first) != self.row(second) {
+                    continue;
+                }
+                for ch in self.alphabet.iter() {
+                    for suffix in self.suffixes.iter() {
+                        let first_word = format!("{}{}{}", first, ch, suffix);
+                        let second_word = format!("{}{}{}", second, ch, suffix);
+                        if self.memberships[&first_word] != self.memberships[&second_word] {
+                            return Some(format!("{}{}", ch, suffix));
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// builds the hypothesis of a closed and consistent table: one state for
+    /// each distinct row of S, and reading a moves from row(s) to row(sa)
+    fn to_hypothesis(&self) -> DFA<usize> {
+        let mut row_to_state: BTreeMap<Vec<bool>, usize> = BTreeMap::new();
+        let mut representatives = vec![];
+        for prefix in self.prefixes.iter() {
+            row_to_state.entry(self.row(prefix)).or_insert_with(|| {
+                representatives.push(prefix.clone());
+                representatives.len() - 1
+            });
+        }
+
+        let transitions = representatives
+            .iter()
+            .map(|prefix| {
+                self.alphabet
+                    .iter()
+                    .map(|ch| (*ch, row_to_state[&self.row(&format!("{}{}", prefix, ch))]))
+                    .collect()
+            })
+            .collect();
+        // the first suffix is the empty word
+        let end_states = representatives
+            .iter()
+            .enumerate()
+            .filter(|(_, prefix)| self.row(prefix)[0])
+            .map(|(state, _)| state)
+            .collect();
+
+        DFA::from_state(
+            representatives.len(),
+            row_to_state[&self.row("")],
+            end_states,
+            transitions,
+            Some(self.alphabet.clone()),
+        )
+    }
+}
+
+/// a step of the learner, with the table after the step and, for the steps
+/// ending with an equivalence query, the hypothesis that was asked
+#[derive(Debug, Clone)]
+pub struct LStarStep {
+    pub description: String,
+    pub table: ObservationTable,
+    pub hypothesis: Option<DFA<usize>>,
+}
+
+/// Angluin's L* algorithm: it keeps the table closed and consistent, and asks
+/// the oracle if the hypothesis of the table is correct. Every prefix of a
+/// counterexample is added to S.
+///
+/// returns the learned DFA, which is the minimal DFA of the language,
+/// together with the steps of the algorithm
+pub fn learn_lstar<O: Oracle>(oracle: &O) -> (DFA<usize>, Vec<LStarStep>) {
+    let mut table = ObservationTable::new(oracle.get_alphabet());
+    table.fill(oracle);
+    let mut steps = vec![LStarStep {
+        description: "initial table, S = E = {ε}".to_string(),
+        table: table.clone(),
+        hypothesis: None,
+    }];
+
+    loop {
+        let description = if let Some(word) = table.find_unclosed() {
+            table.prefixes.push(word.clone());
+            format!("not closed: the row of {} is new, add it to S", word_to_string(&word))
+        } else if let Some(suffix) = table.find_inconsistency() {
+            table.suffixes.push(suffix.clone());
+            format!("not consistent: add the suffix {} to E", word_to_string(&suffix))
+        } else {
+            let hypothesis = table.to_hypothesis();
+            match oracle.equivalence(&hypothesis) {
+                None => {
+                    steps.push(LStarStep {
+                        description: format!(
+                            "the hypothesis with {} states is correct",
+                            hypothesis.get_num_states()
+                        ),
+                        table: table.clone(),
+                        hypothesis: Some(hypothesis.clone()),
+                    });
+                    return (hypothesis, steps);
+                }
+                Some(counterexample) => {
+                    let chars: Vec<char> = counterexample.chars().collect();
+                    for len in 1..=chars.len() {
+                        let prefix: String = chars[..len].iter().collect();
+                        if !table.prefixes.contains(&prefix) {
+                            table.prefixes.push(prefix);
+                        }
+                    }
+                    table.fill(oracle);
+                    steps.push(LStarStep {
+                        description: format!(
+                            "counterexample {} to the hypothesis with {} states, add its prefixes to S",
+                            word_to_string(&counterexample),
+                            hypothesis.get_num_states()
+                        ),
+                        table: table.clone(),
+                        hypothesis: Some(hypothesis),
+                    });
+                    continue;
+                }
+            }
+        };
+
+        table.fill(oracle);
+        steps.push(LStarStep {
+            description,
+            table: table.clone(),
+            hypothesis: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::automata::ReOperator;
+    use crate::learning::RegexOracle;
+
+    fn oracle(regex: &str) -> RegexOracle {
+        RegexOracle::new(&ReOperator::from_string(&regex.to_string()).unwrap())
+    }
+
+    #[test]
+    fn learns_minimal_dfa() {
+        for regex in ["a(b|c)*", "(ab|ba)*a", "(a|b)*abb", "ε|ab", "(aa|b)*"] {
+            let oracle = oracle(regex);
+            let (learned, steps) = learn_lstar(&oracle);
+
+            assert!(learned.is_isomorphic(oracle.get_target()));
+            let hypotheses = steps.iter().filter(|step| step.hypothesis.is_some()).count();
+            assert_eq!(oracle.get_equivalence_queries(), hypotheses);
+        }
+    }
+
+    #[test]
+    fn final_table_is_closed_and_consistent() {
+        let oracle = oracle("(a|b)*a(a|b)");
+        let (_, steps) = learn_lstar(&oracle);
+        let table = &steps.last().unwrap().table;
+
+        assert_eq!(table.find_unclosed(), None);
+        assert_eq!(table.find_inconsistency(), None);
+        // the table remembers the answers, so no query is asked twice
+        assert_eq!(table.get_num_membership_queries(), oracle.get_membership_queries());
+    }
+
+    #[test]
+    fn single_state() {
+        let oracle = oracle("(a|b)*");
+        let (learned, steps) = learn_lstar(&oracle);
+
+        assert_eq!(learned.get_num_states(), 1);
+        assert_eq!(steps.len(), 2);
+    }
+}
@@ -0,0 +1,7 @@
+mod lstar;
+mod oracle;
+mod rpni;
+
+pub use lstar::*;
+pub use oracle::*;
+pub use rpni::*;
@@ -0,0 +1,72 @@
+use std::cell::Cell;
+
+use crate::automata::{NfaStates, ReOperator, DFA, NFA};
+
+/// Minimally adequate teacher of active learning,
+/// it knows the target language and answers the queries of the learner
+pub trait Oracle {
+    fn get_alphabet(&self) -> Vec<char>;
+
+    /// is the word in the target language?
+    fn membership(&self, word: &str) -> bool;
+
+    /// returns None if the hypothesis recognizes the target language,
+    /// otherwise a word accepted by only one of the two
+    fn equivalence(&self, hypothesis: &DFA<usize>) -> Option<String>;
+}
+
+/// Oracle of the language of a regex, the queries are answered
+/// with the minimized DFA of the regex. It counts the queries it receives.
+pub struct RegexOracle {
+    target: DFA<NfaStates>,
+    membership_queries: Cell<usize>,
+    equivalence_queries: Cell<usize>,
+}
+
+impl RegexOracle {
+    pub fn new(regex: &ReOperator) -> Self {
+        Self {
+            target: DFA::from(&NFA::from(regex)).get_minimized_dfa(),
+            membership_queries: Cell::new(0),
+            equivalence_queries: Cell::new(0),
+        }
+    }
+
+    pub fn get_target(&self) -> &DFA<NfaStates> {
+        &self.target
+    }
+
+    pub fn get_membership_queries(&self) -> usize {
+        self.membership_queries.get()
+    }
+
+    pub fn get_equivalence_queries(&self) -> usize {
+        self.equivalence_queries.get()
+    }
+}
+
+impl Oracle for RegexOracle {
+    fn get_alphabet(&self) -> Vec<char> {
+        let mut alphabet = self.target.get_alphabet().clone();
+        alphabet.sort();
+        alphabet
+    }
+
+    fn membership(&self, word: &str) -> bool {
+        self.membership_queries.set(self.membership_queries.get() + 1);
+        self.target.accepts(word)
+    }
+
+    /// the counterexample is the shortest word in one of the two differences
+    fn equivalence(&self, hypothesis: &DFA<usize>) -> Option<String> {
+        self.equivalence_queries.set(self.equivalence_queries.get() + 1);
+        let missing = self.target.get_subset_witness(hypothesis);
+        let extra = hypothesis.get_subset_witness(&self.target);
+
+        match (missing, extra) {
+            (Some(missing), Some(extra)) if extra.chars().count() < missing.chars().count() => Some(extra),
+            (Some(missing), _) => Some(missing),
+            (None, extra) => extra,
+        }
+    }
+}
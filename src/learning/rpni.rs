@@ -0,0 +1,313 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+use crate::automata::{word_to_string, DFA};
+use crate::error::InvalidSample;
+use crate::utils::{DisjointUnionFind, Graph};
+
+/// labelled examples of a language
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Samples {
+    pub positive: BTreeSet<String>,
+    pub negative: BTreeSet<String>,
+}
+
+impl Samples {
+    /// parses one sample for each line: "+ word" for a positive sample and "- word"
+    /// for a negative one, the empty word is written as "+" or "+ ε".
+    /// Empty lines and lines starting with # are skipped.
+    pub fn from_text(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut samples = Self::default();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut chars = line.chars();
+            let label = chars.next();
+            let word: String = chars.filter(|ch| !ch.is_whitespace() && *ch != 'ε').collect();
+            let (same, other) = match label {
+                Some('+') => (&mut samples.positive, &samples.negative),
+                Some('-') => (&mut samples.negative, &samples.positive),
+                _ => return Err(Box::new(InvalidSample::new(index + 1, line.to_string()))),
+            };
+            if other.contains(&word) {
+                // the same word is both positive and negative
+                return Err(Box::new(InvalidSample::new(index + 1, line.to_string())));
+            }
+            same.insert(word);
+        }
+        Ok(samples)
+    }
+
+    /// returns true if the DFA accepts all the positive samples and rejects all the negative ones
+    pub fn is_consistent<T>(&self, dfa: &DFA<T>) -> bool {
+        self.positive.iter().all(|word| dfa.accepts(word))
+            && self.negative.iter().all(|word| !dfa.accepts(word))
+    }
+}
+
+/// DFA obtained merging the states of the prefix tree acceptor,
+/// each class of the union find is a state.
+///
+/// The transitions of a class are stored in its representative, and point to
+/// states of the prefix tree (the target class is found with the union find).
+#[derive(Debug, Clone)]
+struct Quotient {
+    classes: DisjointUnionFind,
+    transitions: Vec<BTreeMap<char, usize>>,
+    finals: Vec<bool>,
+}
+
+impl Quotient {
+    /// merges the classes of the two states, and then the classes reached with the
+    /// same character from both, so the quotient stays deterministic
+    fn merge(&mut self, first: usize, second: usize) {
+        let first = self.classes.find(first);
+        let second = self.classes.find(second);
+        if first == second {
+            return;
+        }
+
+        let mut transitions = std::mem::take(&mut self.transitions[first]);
+        let other_transitions = std::mem::take(&mut self.transitions[second]);
+        let is_final = self.finals[first] || self.finals[second];
+
+        let mut to_merge = vec![];
+        for (ch, to) in other_transitions {
+            match transitions.get(&ch) {
+                Some(other_to) => to_merge.push((*other_to, to)),
+                None => {
+                    transitions.insert(ch, to);
+                }
+            }
+        }
+
+        self.classes.join(first, second);
+        let head = self.classes.find(first);
+        self.transitions[head] = transitions;
+        self.finals[head] = is_final;
+
+        for (first, second) in to_merge {
+            self.merge(first, second);
+        }
+    }
+
+    /// returns the smallest state of the prefix tree in the class of the state
+    fn smallest_state(&mut self, state: usize) -> usize {
+        let head = self.classes.find(state);
+        (0..self.finals.len())
+            .find(|other| self.classes.find(*other) == head)
+            .unwrap()
+    }
+
+    fn accepts(&mut self, word: &str) -> bool {
+        let mut state = self.classes.find(0);
+        for ch in word.chars() {
+            match self.transitions[state].get(&ch) {
+                Some(next) => state = self.classes.find(*next),
+                None => return false,
+            }
+        }
+        self.finals[state]
+    }
+
+    /// returns the DFA of the quotient, the states are numbered following
+    /// the smallest prefix tree state of each class
+    fn get_dfa(&mut self, alphabet: &[char]) -> DFA<usize> {
+        let heads: Vec<usize> = (0..self.finals.len())
+            .filter(|state| self.classes.is_head(state))
+            .collect();
+        let mut head_to_state = BTreeMap::new();
+        for state in 0..self.finals.len() {
+            let head = self.classes.find(state);
+            let new_index = head_to_state.len();
+            head_to_state.entry(head).or_insert(new_index);
+        }
+
+        let mut transitions = vec![BTreeMap::new(); heads.len()];
+        let mut end_states = vec![];
+        for head in heads {
+            let state = head_to_state[&head];
+            for (ch, to) in self.transitions[head].clone() {
+                transitions[state].insert(ch, head_to_state[&self.classes.find(to)]);
+            }
+            if self.finals[head] {
+                end_states.push(state);
+            }
+        }
+
+        let start_state = head_to_state[&self.classes.find(0)];
+        DFA::from_state(
+            transitions.len(),
+            start_state,
+            end_states,
+            transitions,
+            Some(alphabet.to_vec()),
+        )
+    }
+}
+
+/// RPNI: builds the prefix tree acceptor of the positive samples, then tries to merge
+/// every blue state (a child of the red states) with the red states in shortlex order.
+/// A merge is kept if the quotient still rejects all the negative samples,
+/// if no merge is possible the blue state becomes red.
+///
+/// returns the inferred DFA, consistent with the samples, together with the
+/// description and the graph of the prefix tree, of each merge attempt and of the result
+pub fn learn_rpni(samples: &Samples) -> (DFA<usize>, Vec<(String, Graph)>) {
+    // the states of the prefix tree are the prefixes of the positive samples in shortlex order
+    let mut prefixes: Vec<String> = samples
+        .positive
+        .iter()
+        .flat_map(|word| {
+            let chars: Vec<char> = word.chars().collect();
+            (0..=chars.len()).map(move |len| chars[..len].iter().collect::<String>())
+        })
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect();
+    if prefixes.is_empty() {
+        prefixes.push(String::new());
+    }
+    prefixes.sort_by_key(|prefix| prefix.chars().count());
+    let prefix_to_state: BTreeMap<&String, usize> =
+        prefixes.iter().enumerate().map(|(state, prefix)| (prefix, state)).collect();
+
+    let mut transitions = vec![BTreeMap::new(); prefixes.len()];
+    for (state, prefix) in prefixes.iter().enumerate().skip(1) {
+        let mut parent = prefix.clone();
+        let ch = parent.pop().unwrap();
+        transitions[prefix_to_state[&parent]].insert(ch, state);
+    }
+    let alphabet: Vec<char> = prefixes
+        .iter()
+        .flat_map(|prefix| prefix.chars())
+        .chain(samples.negative.iter().flat_map(|word| word.chars()))
+        .collect::<BTreeSet<char>>()
+        .into_iter()
+        .collect();
+
+    let mut quotient = Quotient {
+        classes: DisjointUnionFind::new(prefixes.len()),
+        transitions,
+        finals: prefixes.iter().map(|prefix| samples.positive.contains(prefix)).collect(),
+    };
+    let mut steps = vec![("prefix tree acceptor".to_string(), quotient.get_dfa(&alphabet).into())];
+
+    // red states are never merged together, each one is the smallest state of its class
+    let mut red = vec![0];
+    loop {
+        // the blue states are the children of the red ones that are not red
+        let mut children = vec![];
+        for state in red.iter() {
+            let head = quotient.classes.find(*state);
+            children.extend(quotient.transitions[head].values().cloned());
+        }
+        let blue = children
+            .into_iter()
+            .map(|state| quotient.smallest_state(state))
+            .filter(|state| !red.contains(state))
+            .min();
+        let blue = match blue {
+            Some(blue) => blue,
+            None => break,
+        };
+
+        let mut merged = false;
+        for red_state in red.iter() {
+            let mut attempt = quotient.clone();
+            attempt.merge(*red_state, blue);
+            let accepted_negative = samples
+                .negative
+                .iter()
+                .find(|word| attempt.accepts(word))
+                .cloned();
+
+            let description = format!("merge {} into {}", word_to_string(&prefixes[blue]), word_to_string(&prefixes[*red_state]));
+            match accepted_negative {
+                None => {
+                    steps.push((format!("{}: consistent", description), attempt.get_dfa(&alphabet).into()));
+                    quotient = attempt;
+                    merged = true;
+                    break;
+                }
+                Some(word) => steps.push((
+                    format!("{}: rejected, it accepts the negative sample {}", description, word_to_string(&word)),
+                    attempt.get_dfa(&alphabet).into(),
+                )),
+            }
+        }
+
+        if !merged {
+            red.push(blue);
+            steps.push((
+                format!("{} can't be merged, it becomes red", word_to_string(&prefixes[blue])),
+                quotient.get_dfa(&alphabet).into(),
+            ));
+        }
+    }
+
+    let dfa = quotient.get_dfa(&alphabet).get_canonical_dfa();
+    steps.push(("inferred DFA".to_string(), dfa.clone().into()));
+    (dfa, steps)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::automata::test_utils::{dfa_from_regex, words_up_to};
+
+    /// labels all the words up to max_len with the language of the regex
+    fn samples_of(regex: &str, alphabet: &[char], max_len: usize) -> Samples {
+        let dfa = dfa_from_regex(regex);
+        let words = words_up_to(alphabet, max_len);
+        let (positive, negative) = words.into_iter().partition(|word| dfa.accepts(word));
+        Samples { positive, negative }
+    }
+
+    #[test]
+    fn parse_samples() {
+        let samples = Samples::from_text("# comment\n+ ab\n+ ε\n- b a\n\n-bb").unwrap();
+
+        assert_eq!(samples.positive, BTreeSet::from(["ab".to_string(), String::new()]));
+        assert_eq!(samples.negative, BTreeSet::from(["ba".to_string(), "bb".to_string()]));
+        assert!(Samples::from_text("+ ab\nab").is_err());
+        // the same word can't be both positive and negative
+        assert!(Samples::from_text("+ ab\n- ab").is_err());
+        assert!(Samples::from_text("+\n-ε").is_err());
+    }
+
+    #[test]
+    fn consistent_with_samples() {
+        for regex in ["a(a|b)*", "(ab)*", "(a|b)*bb(a|b)*", "a*b*"] {
+            let samples = samples_of(regex, &['a', 'b'], 5);
+            let (dfa, steps) = learn_rpni(&samples);
+
+            assert!(samples.is_consistent(&dfa));
+            assert_eq!(steps.first().unwrap().0, "prefix tree acceptor");
+        }
+    }
+
+    #[test]
+    fn identifies_language() {
+        // all the words up to length 5 are a characteristic sample of these languages
+        for regex in ["a(a|b)*", "(ab)*", "a*b*"] {
+            let (dfa, _) = learn_rpni(&samples_of(regex, &['a', 'b'], 5));
+            let target = dfa_from_regex(regex).trim();
+
+            assert!(dfa.is_isomorphic(&target));
+        }
+    }
+
+    #[test]
+    fn only_positive_samples() {
+        // without negative samples everything is merged in a single state
+        let samples = Samples::from_text("+ ab\n+ abb\n+ b").unwrap();
+        let (dfa, _) = learn_rpni(&samples);
+
+        assert_eq!(dfa.get_num_states(), 1);
+        assert!(samples.is_consistent(&dfa));
+    }
+}
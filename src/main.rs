@@ -4,6 +4,7 @@ mod utils;
 mod display;
 mod automata;
 mod grammar;
+mod learning;
 mod error;
 
 #[macro_use]
@@ -1,6 +1,6 @@
 /// Disjoint union find with rank heuristic and path compression.
 /// Uses negative values to count and store the rank.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DisjointUnionFind {
     parent: Vec<i32>,
     num_sets: usize,